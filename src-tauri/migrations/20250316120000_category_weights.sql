@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS CATEGORY_WEIGHTS (
+    ID INTEGER PRIMARY KEY,
+    CLASS_ID INTEGER NOT NULL,
+    ASSIGNMENT_TYPE TEXT NOT NULL CHECK (
+        ASSIGNMENT_TYPE IN ('Homework', 'Test')
+    ),
+    WEIGHT REAL NOT NULL CHECK (WEIGHT >= 0),
+    UNIQUE (CLASS_ID, ASSIGNMENT_TYPE),
+    FOREIGN KEY (CLASS_ID) REFERENCES CLASSES (ID) ON DELETE CASCADE
+);
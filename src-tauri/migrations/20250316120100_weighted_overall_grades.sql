@@ -0,0 +1,51 @@
+DROP VIEW IF EXISTS OVERALL_GRADES;
+
+-- Each category is averaged on its own and the category averages are then
+-- combined using the class weights. Only categories that have both a weight
+-- and at least one grade take part, so the weights are renormalised over
+-- them. Classes without any weights fall back to the flat average.
+CREATE VIEW OVERALL_GRADES AS
+WITH CATEGORY_AVERAGES AS (
+    SELECT
+        s.STUDENT_ID,
+        a.CLASS_ID,
+        a.ASSIGNMENT_TYPE,
+        AVG(g.SCORE / a.MAXIMUM_SCORE * 100) AS CATEGORY_PERCENTAGE,
+        COUNT(*) AS GRADE_COUNT
+    FROM
+        GRADES g
+    JOIN
+        ASSIGNMENTS a ON g.ASSIGNMENT_ID = a.ID
+    JOIN
+        STUDENT_CLASSES s ON g.STUDENT_ID = s.STUDENT_ID AND a.CLASS_ID = s.CLASS_ID
+    GROUP BY
+        s.STUDENT_ID, a.CLASS_ID, a.ASSIGNMENT_TYPE
+),
+STUDENT_PERCENTAGES AS (
+    SELECT
+        c.STUDENT_ID,
+        c.CLASS_ID,
+        COALESCE(
+            SUM(c.CATEGORY_PERCENTAGE * w.WEIGHT) / NULLIF(SUM(w.WEIGHT), 0),
+            SUM(c.CATEGORY_PERCENTAGE * c.GRADE_COUNT) / SUM(c.GRADE_COUNT)
+        ) AS PERCENTAGE
+    FROM
+        CATEGORY_AVERAGES c
+    LEFT JOIN
+        CATEGORY_WEIGHTS w ON w.CLASS_ID = c.CLASS_ID AND w.ASSIGNMENT_TYPE = c.ASSIGNMENT_TYPE
+    GROUP BY
+        c.STUDENT_ID, c.CLASS_ID
+)
+SELECT
+    STUDENT_ID,
+    CLASS_ID,
+    PERCENTAGE,
+    CASE
+        WHEN PERCENTAGE >= 90 THEN 'A'
+        WHEN PERCENTAGE >= 80 THEN 'B'
+        WHEN PERCENTAGE >= 70 THEN 'C'
+        WHEN PERCENTAGE >= 60 THEN 'D'
+        ELSE 'F'
+    END AS LETTER_GRADE
+FROM
+    STUDENT_PERCENTAGES;
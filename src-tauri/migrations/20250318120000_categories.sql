@@ -0,0 +1,126 @@
+CREATE TABLE IF NOT EXISTS CATEGORIES (
+    ID INTEGER PRIMARY KEY,
+    CLASS_ID INTEGER NOT NULL,
+    CATEGORY_NAME TEXT NOT NULL,
+    WEIGHT REAL CHECK (WEIGHT >= 0),
+    UNIQUE (CLASS_ID, CATEGORY_NAME),
+    FOREIGN KEY (CLASS_ID) REFERENCES CLASSES (ID) ON DELETE CASCADE
+);
+
+-- Carry the existing weights and every assignment type in use over as
+-- categories of their class.
+INSERT INTO CATEGORIES (CLASS_ID, CATEGORY_NAME, WEIGHT)
+SELECT CLASS_ID, ASSIGNMENT_TYPE, WEIGHT FROM CATEGORY_WEIGHTS;
+
+INSERT OR IGNORE INTO CATEGORIES (CLASS_ID, CATEGORY_NAME)
+SELECT DISTINCT CLASS_ID, ASSIGNMENT_TYPE FROM ASSIGNMENTS;
+
+DROP VIEW IF EXISTS OVERALL_GRADES;
+DROP TRIGGER IF EXISTS CHECK_SCORE_MAX;
+DROP TRIGGER IF EXISTS CHECK_SCORE_MAX_UPDATE;
+
+-- Dropping ASSIGNMENTS cascades into GRADES, so keep a copy to restore.
+CREATE TABLE GRADES_BACKUP AS SELECT * FROM GRADES;
+
+CREATE TABLE ASSIGNMENTS_NEW (
+    ID INTEGER PRIMARY KEY,
+    CLASS_ID INTEGER NOT NULL,
+    CATEGORY_ID INTEGER NOT NULL,
+    ASSIGNMENT_NAME TEXT NOT NULL,
+    MAXIMUM_SCORE REAL NOT NULL,
+    DUE_DATE TIMESTAMP,
+    FOREIGN KEY (CLASS_ID) REFERENCES CLASSES (ID) ON DELETE CASCADE,
+    FOREIGN KEY (CATEGORY_ID) REFERENCES CATEGORIES (ID)
+);
+
+INSERT INTO ASSIGNMENTS_NEW (ID, CLASS_ID, CATEGORY_ID, ASSIGNMENT_NAME, MAXIMUM_SCORE, DUE_DATE)
+SELECT a.ID, a.CLASS_ID, c.ID, a.ASSIGNMENT_NAME, a.MAXIMUM_SCORE, a.DUE_DATE
+FROM ASSIGNMENTS a
+JOIN CATEGORIES c ON c.CLASS_ID = a.CLASS_ID AND c.CATEGORY_NAME = a.ASSIGNMENT_TYPE;
+
+DROP TABLE ASSIGNMENTS;
+ALTER TABLE ASSIGNMENTS_NEW RENAME TO ASSIGNMENTS;
+
+INSERT INTO GRADES (STUDENT_ID, ASSIGNMENT_ID, SCORE)
+SELECT STUDENT_ID, ASSIGNMENT_ID, SCORE FROM GRADES_BACKUP;
+
+DROP TABLE GRADES_BACKUP;
+DROP TABLE CATEGORY_WEIGHTS;
+
+CREATE TRIGGER CHECK_SCORE_MAX
+BEFORE INSERT ON GRADES
+FOR EACH ROW
+  WHEN NEW.SCORE > (SELECT MAXIMUM_SCORE FROM ASSIGNMENTS WHERE ID = NEW.ASSIGNMENT_ID)
+BEGIN
+  SELECT RAISE(ABORT, 'Score cannot exceed the assignment maximum score');
+END;
+
+CREATE TRIGGER CHECK_SCORE_MAX_UPDATE
+BEFORE UPDATE ON GRADES
+FOR EACH ROW
+WHEN NEW.SCORE > (SELECT MAXIMUM_SCORE FROM ASSIGNMENTS WHERE ID = NEW.ASSIGNMENT_ID)
+BEGIN
+  SELECT RAISE(ABORT, 'Score cannot exceed the assignment maximum score');
+END;
+
+CREATE TRIGGER CHECK_ASSIGNMENT_CATEGORY
+BEFORE INSERT ON ASSIGNMENTS
+FOR EACH ROW
+  WHEN NEW.CLASS_ID IS NOT (SELECT CLASS_ID FROM CATEGORIES WHERE ID = NEW.CATEGORY_ID)
+BEGIN
+  SELECT RAISE(ABORT, 'Category does not belong to the assignment class');
+END;
+
+CREATE TRIGGER CHECK_ASSIGNMENT_CATEGORY_UPDATE
+BEFORE UPDATE ON ASSIGNMENTS
+FOR EACH ROW
+WHEN NEW.CLASS_ID IS NOT (SELECT CLASS_ID FROM CATEGORIES WHERE ID = NEW.CATEGORY_ID)
+BEGIN
+  SELECT RAISE(ABORT, 'Category does not belong to the assignment class');
+END;
+
+CREATE VIEW OVERALL_GRADES AS
+WITH CATEGORY_AVERAGES AS (
+    SELECT
+        s.STUDENT_ID,
+        a.CLASS_ID,
+        a.CATEGORY_ID,
+        AVG(g.SCORE / a.MAXIMUM_SCORE * 100) AS CATEGORY_PERCENTAGE,
+        COUNT(*) AS GRADE_COUNT
+    FROM
+        GRADES g
+    JOIN
+        ASSIGNMENTS a ON g.ASSIGNMENT_ID = a.ID
+    JOIN
+        STUDENT_CLASSES s ON g.STUDENT_ID = s.STUDENT_ID AND a.CLASS_ID = s.CLASS_ID
+    GROUP BY
+        s.STUDENT_ID, a.CLASS_ID, a.CATEGORY_ID
+),
+STUDENT_PERCENTAGES AS (
+    SELECT
+        ca.STUDENT_ID,
+        ca.CLASS_ID,
+        COALESCE(
+            SUM(ca.CATEGORY_PERCENTAGE * c.WEIGHT) / NULLIF(SUM(c.WEIGHT), 0),
+            SUM(ca.CATEGORY_PERCENTAGE * ca.GRADE_COUNT) / SUM(ca.GRADE_COUNT)
+        ) AS PERCENTAGE
+    FROM
+        CATEGORY_AVERAGES ca
+    JOIN
+        CATEGORIES c ON c.ID = ca.CATEGORY_ID
+    GROUP BY
+        ca.STUDENT_ID, ca.CLASS_ID
+)
+SELECT
+    STUDENT_ID,
+    CLASS_ID,
+    PERCENTAGE,
+    CASE
+        WHEN PERCENTAGE >= 90 THEN 'A'
+        WHEN PERCENTAGE >= 80 THEN 'B'
+        WHEN PERCENTAGE >= 70 THEN 'C'
+        WHEN PERCENTAGE >= 60 THEN 'D'
+        ELSE 'F'
+    END AS LETTER_GRADE
+FROM
+    STUDENT_PERCENTAGES;
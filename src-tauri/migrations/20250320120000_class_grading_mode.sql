@@ -0,0 +1,64 @@
+ALTER TABLE CLASSES ADD COLUMN GRADING_MODE TEXT NOT NULL DEFAULT 'WeightedCategories' CHECK (
+    GRADING_MODE IN ('PercentAverage', 'TotalPoints', 'WeightedCategories')
+);
+
+DROP VIEW IF EXISTS OVERALL_GRADES;
+
+CREATE VIEW OVERALL_GRADES AS
+WITH CATEGORY_AVERAGES AS (
+    SELECT
+        s.STUDENT_ID,
+        a.CLASS_ID,
+        a.CATEGORY_ID,
+        AVG(g.SCORE / a.MAXIMUM_SCORE * 100) AS CATEGORY_PERCENTAGE,
+        COUNT(*) AS GRADE_COUNT,
+        SUM(g.SCORE) AS POINTS_EARNED,
+        SUM(a.MAXIMUM_SCORE) AS POINTS_POSSIBLE
+    FROM
+        GRADES g
+    JOIN
+        ASSIGNMENTS a ON g.ASSIGNMENT_ID = a.ID
+    JOIN
+        STUDENT_CLASSES s ON g.STUDENT_ID = s.STUDENT_ID AND a.CLASS_ID = s.CLASS_ID
+    GROUP BY
+        s.STUDENT_ID, a.CLASS_ID, a.CATEGORY_ID
+),
+STUDENT_PERCENTAGES AS (
+    SELECT
+        ca.STUDENT_ID,
+        ca.CLASS_ID,
+        cl.GRADING_MODE,
+        CASE cl.GRADING_MODE
+            WHEN 'PercentAverage' THEN
+                SUM(ca.CATEGORY_PERCENTAGE * ca.GRADE_COUNT) / SUM(ca.GRADE_COUNT)
+            WHEN 'TotalPoints' THEN
+                SUM(ca.POINTS_EARNED) / SUM(ca.POINTS_POSSIBLE) * 100
+            ELSE
+                COALESCE(
+                    SUM(ca.CATEGORY_PERCENTAGE * c.WEIGHT) / NULLIF(SUM(c.WEIGHT), 0),
+                    SUM(ca.CATEGORY_PERCENTAGE * ca.GRADE_COUNT) / SUM(ca.GRADE_COUNT)
+                )
+        END AS PERCENTAGE
+    FROM
+        CATEGORY_AVERAGES ca
+    JOIN
+        CATEGORIES c ON c.ID = ca.CATEGORY_ID
+    JOIN
+        CLASSES cl ON cl.ID = ca.CLASS_ID
+    GROUP BY
+        ca.STUDENT_ID, ca.CLASS_ID, cl.GRADING_MODE
+)
+SELECT
+    STUDENT_ID,
+    CLASS_ID,
+    GRADING_MODE,
+    PERCENTAGE,
+    CASE
+        WHEN PERCENTAGE >= 90 THEN 'A'
+        WHEN PERCENTAGE >= 80 THEN 'B'
+        WHEN PERCENTAGE >= 70 THEN 'C'
+        WHEN PERCENTAGE >= 60 THEN 'D'
+        ELSE 'F'
+    END AS LETTER_GRADE
+FROM
+    STUDENT_PERCENTAGES;
@@ -0,0 +1,121 @@
+CREATE TABLE IF NOT EXISTS GRADING_SCALES (
+    ID INTEGER PRIMARY KEY,
+    SCALE_NAME TEXT NOT NULL UNIQUE,
+    IS_DEFAULT INTEGER NOT NULL DEFAULT 0 CHECK (IS_DEFAULT IN (0, 1))
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS IDX_GRADING_SCALES_DEFAULT
+ON GRADING_SCALES (IS_DEFAULT) WHERE IS_DEFAULT = 1;
+
+CREATE TABLE IF NOT EXISTS GRADING_SCALE_THRESHOLDS (
+    SCALE_ID INTEGER NOT NULL,
+    LETTER_GRADE TEXT NOT NULL,
+    MIN_PERCENTAGE REAL NOT NULL,
+    PRIMARY KEY (SCALE_ID, LETTER_GRADE),
+    FOREIGN KEY (SCALE_ID) REFERENCES GRADING_SCALES (ID) ON DELETE CASCADE
+);
+
+CREATE TRIGGER PREVENT_DEFAULT_SCALE_DELETE
+BEFORE DELETE ON GRADING_SCALES
+FOR EACH ROW
+  WHEN OLD.IS_DEFAULT = 1
+BEGIN
+  SELECT RAISE(ABORT, 'The default grading scale cannot be deleted');
+END;
+
+INSERT INTO GRADING_SCALES (ID, SCALE_NAME, IS_DEFAULT) VALUES
+    (1, 'Standard', 1),
+    (2, 'Plus/Minus', 0),
+    (3, 'Pass/Fail', 0);
+
+INSERT INTO GRADING_SCALE_THRESHOLDS (SCALE_ID, LETTER_GRADE, MIN_PERCENTAGE) VALUES
+    (1, 'A', 90),
+    (1, 'B', 80),
+    (1, 'C', 70),
+    (1, 'D', 60),
+    (1, 'F', 0),
+    (2, 'A', 93),
+    (2, 'A-', 90),
+    (2, 'B+', 87),
+    (2, 'B', 83),
+    (2, 'B-', 80),
+    (2, 'C+', 77),
+    (2, 'C', 73),
+    (2, 'C-', 70),
+    (2, 'D+', 67),
+    (2, 'D', 63),
+    (2, 'D-', 60),
+    (2, 'F', 0),
+    (3, 'P', 60),
+    (3, 'F', 0);
+
+-- Classes without a scale of their own use the default scale.
+ALTER TABLE CLASSES ADD COLUMN GRADING_SCALE_ID INTEGER REFERENCES GRADING_SCALES (ID) ON DELETE SET NULL;
+
+DROP VIEW IF EXISTS OVERALL_GRADES;
+
+CREATE VIEW OVERALL_GRADES AS
+WITH CATEGORY_AVERAGES AS (
+    SELECT
+        s.STUDENT_ID,
+        a.CLASS_ID,
+        a.CATEGORY_ID,
+        AVG(g.SCORE / a.MAXIMUM_SCORE * 100) AS CATEGORY_PERCENTAGE,
+        COUNT(*) AS GRADE_COUNT,
+        SUM(g.SCORE) AS POINTS_EARNED,
+        SUM(a.MAXIMUM_SCORE) AS POINTS_POSSIBLE
+    FROM
+        GRADES g
+    JOIN
+        ASSIGNMENTS a ON g.ASSIGNMENT_ID = a.ID
+    JOIN
+        STUDENT_CLASSES s ON g.STUDENT_ID = s.STUDENT_ID AND a.CLASS_ID = s.CLASS_ID
+    GROUP BY
+        s.STUDENT_ID, a.CLASS_ID, a.CATEGORY_ID
+),
+STUDENT_PERCENTAGES AS (
+    SELECT
+        ca.STUDENT_ID,
+        ca.CLASS_ID,
+        cl.GRADING_MODE,
+        COALESCE(
+            cl.GRADING_SCALE_ID,
+            (SELECT ID FROM GRADING_SCALES WHERE IS_DEFAULT = 1)
+        ) AS SCALE_ID,
+        CASE cl.GRADING_MODE
+            WHEN 'PercentAverage' THEN
+                SUM(ca.CATEGORY_PERCENTAGE * ca.GRADE_COUNT) / SUM(ca.GRADE_COUNT)
+            WHEN 'TotalPoints' THEN
+                SUM(ca.POINTS_EARNED) / SUM(ca.POINTS_POSSIBLE) * 100
+            ELSE
+                COALESCE(
+                    SUM(ca.CATEGORY_PERCENTAGE * c.WEIGHT) / NULLIF(SUM(c.WEIGHT), 0),
+                    SUM(ca.CATEGORY_PERCENTAGE * ca.GRADE_COUNT) / SUM(ca.GRADE_COUNT)
+                )
+        END AS PERCENTAGE
+    FROM
+        CATEGORY_AVERAGES ca
+    JOIN
+        CATEGORIES c ON c.ID = ca.CATEGORY_ID
+    JOIN
+        CLASSES cl ON cl.ID = ca.CLASS_ID
+    GROUP BY
+        ca.STUDENT_ID, ca.CLASS_ID, cl.GRADING_MODE, cl.GRADING_SCALE_ID
+)
+SELECT
+    sp.STUDENT_ID,
+    sp.CLASS_ID,
+    sp.GRADING_MODE,
+    sp.PERCENTAGE,
+    -- The highest threshold reached, or the lowest one when none is.
+    COALESCE(
+        (SELECT t.LETTER_GRADE FROM GRADING_SCALE_THRESHOLDS t
+         WHERE t.SCALE_ID = sp.SCALE_ID AND sp.PERCENTAGE >= t.MIN_PERCENTAGE
+         ORDER BY t.MIN_PERCENTAGE DESC LIMIT 1),
+        (SELECT t.LETTER_GRADE FROM GRADING_SCALE_THRESHOLDS t
+         WHERE t.SCALE_ID = sp.SCALE_ID
+         ORDER BY t.MIN_PERCENTAGE ASC LIMIT 1),
+        ''
+    ) AS LETTER_GRADE
+FROM
+    STUDENT_PERCENTAGES sp;
@@ -0,0 +1,115 @@
+ALTER TABLE CATEGORIES ADD COLUMN DROP_LOWEST INTEGER NOT NULL DEFAULT 0 CHECK (DROP_LOWEST >= 0);
+ALTER TABLE CATEGORIES ADD COLUMN DROP_HIGHEST INTEGER NOT NULL DEFAULT 0 CHECK (DROP_HIGHEST >= 0);
+
+-- Every grade of an enrolled student together with whether the drop rules
+-- of its category exclude it. Rules only apply while the student keeps at
+-- least one grade in the category.
+CREATE VIEW COUNTED_GRADES AS
+WITH RANKED_GRADES AS (
+    SELECT
+        g.STUDENT_ID,
+        g.ASSIGNMENT_ID,
+        a.CLASS_ID,
+        a.CATEGORY_ID,
+        g.SCORE,
+        a.MAXIMUM_SCORE,
+        g.SCORE / a.MAXIMUM_SCORE * 100 AS PERCENTAGE,
+        ROW_NUMBER() OVER (
+            PARTITION BY g.STUDENT_ID, a.CATEGORY_ID
+            ORDER BY g.SCORE / a.MAXIMUM_SCORE ASC, g.ASSIGNMENT_ID ASC
+        ) AS LOWEST_RANK,
+        ROW_NUMBER() OVER (
+            PARTITION BY g.STUDENT_ID, a.CATEGORY_ID
+            ORDER BY g.SCORE / a.MAXIMUM_SCORE DESC, g.ASSIGNMENT_ID DESC
+        ) AS HIGHEST_RANK,
+        COUNT(*) OVER (PARTITION BY g.STUDENT_ID, a.CATEGORY_ID) AS GRADE_COUNT
+    FROM
+        GRADES g
+    JOIN
+        ASSIGNMENTS a ON g.ASSIGNMENT_ID = a.ID
+    JOIN
+        STUDENT_CLASSES s ON g.STUDENT_ID = s.STUDENT_ID AND a.CLASS_ID = s.CLASS_ID
+)
+SELECT
+    r.STUDENT_ID,
+    r.ASSIGNMENT_ID,
+    r.CLASS_ID,
+    r.CATEGORY_ID,
+    r.SCORE,
+    r.MAXIMUM_SCORE,
+    r.PERCENTAGE,
+    CASE
+        WHEN r.GRADE_COUNT > c.DROP_LOWEST + c.DROP_HIGHEST
+            AND (r.LOWEST_RANK <= c.DROP_LOWEST OR r.HIGHEST_RANK <= c.DROP_HIGHEST)
+        THEN 1
+        ELSE 0
+    END AS IS_DROPPED
+FROM
+    RANKED_GRADES r
+JOIN
+    CATEGORIES c ON c.ID = r.CATEGORY_ID;
+
+DROP VIEW IF EXISTS OVERALL_GRADES;
+
+CREATE VIEW OVERALL_GRADES AS
+WITH CATEGORY_AVERAGES AS (
+    SELECT
+        STUDENT_ID,
+        CLASS_ID,
+        CATEGORY_ID,
+        AVG(PERCENTAGE) AS CATEGORY_PERCENTAGE,
+        COUNT(*) AS GRADE_COUNT,
+        SUM(SCORE) AS POINTS_EARNED,
+        SUM(MAXIMUM_SCORE) AS POINTS_POSSIBLE
+    FROM
+        COUNTED_GRADES
+    WHERE
+        IS_DROPPED = 0
+    GROUP BY
+        STUDENT_ID, CLASS_ID, CATEGORY_ID
+),
+STUDENT_PERCENTAGES AS (
+    SELECT
+        ca.STUDENT_ID,
+        ca.CLASS_ID,
+        cl.GRADING_MODE,
+        COALESCE(
+            cl.GRADING_SCALE_ID,
+            (SELECT ID FROM GRADING_SCALES WHERE IS_DEFAULT = 1)
+        ) AS SCALE_ID,
+        CASE cl.GRADING_MODE
+            WHEN 'PercentAverage' THEN
+                SUM(ca.CATEGORY_PERCENTAGE * ca.GRADE_COUNT) / SUM(ca.GRADE_COUNT)
+            WHEN 'TotalPoints' THEN
+                SUM(ca.POINTS_EARNED) / SUM(ca.POINTS_POSSIBLE) * 100
+            ELSE
+                COALESCE(
+                    SUM(ca.CATEGORY_PERCENTAGE * c.WEIGHT) / NULLIF(SUM(c.WEIGHT), 0),
+                    SUM(ca.CATEGORY_PERCENTAGE * ca.GRADE_COUNT) / SUM(ca.GRADE_COUNT)
+                )
+        END AS PERCENTAGE
+    FROM
+        CATEGORY_AVERAGES ca
+    JOIN
+        CATEGORIES c ON c.ID = ca.CATEGORY_ID
+    JOIN
+        CLASSES cl ON cl.ID = ca.CLASS_ID
+    GROUP BY
+        ca.STUDENT_ID, ca.CLASS_ID, cl.GRADING_MODE, cl.GRADING_SCALE_ID
+)
+SELECT
+    sp.STUDENT_ID,
+    sp.CLASS_ID,
+    sp.GRADING_MODE,
+    sp.PERCENTAGE,
+    COALESCE(
+        (SELECT t.LETTER_GRADE FROM GRADING_SCALE_THRESHOLDS t
+         WHERE t.SCALE_ID = sp.SCALE_ID AND sp.PERCENTAGE >= t.MIN_PERCENTAGE
+         ORDER BY t.MIN_PERCENTAGE DESC LIMIT 1),
+        (SELECT t.LETTER_GRADE FROM GRADING_SCALE_THRESHOLDS t
+         WHERE t.SCALE_ID = sp.SCALE_ID
+         ORDER BY t.MIN_PERCENTAGE ASC LIMIT 1),
+        ''
+    ) AS LETTER_GRADE
+FROM
+    STUDENT_PERCENTAGES sp;
@@ -0,0 +1,3 @@
+-- Overall grades are calculated by the grading module instead.
+DROP VIEW IF EXISTS OVERALL_GRADES;
+DROP VIEW IF EXISTS COUNTED_GRADES;
@@ -0,0 +1,34 @@
+CREATE TABLE GRADES_NEW (
+    STUDENT_ID INTEGER NOT NULL,
+    ASSIGNMENT_ID INTEGER NOT NULL,
+    SCORE REAL CHECK (SCORE >= 0),
+    STATUS TEXT NOT NULL DEFAULT 'Graded' CHECK (
+        STATUS IN ('Graded', 'Excused', 'Missing', 'Incomplete')
+    ),
+    CHECK (STATUS <> 'Graded' OR SCORE IS NOT NULL),
+    PRIMARY KEY (STUDENT_ID, ASSIGNMENT_ID),
+    FOREIGN KEY (STUDENT_ID) REFERENCES STUDENTS (ID) ON DELETE CASCADE,
+    FOREIGN KEY (ASSIGNMENT_ID) REFERENCES ASSIGNMENTS (ID) ON DELETE CASCADE
+);
+
+INSERT INTO GRADES_NEW (STUDENT_ID, ASSIGNMENT_ID, SCORE, STATUS)
+SELECT STUDENT_ID, ASSIGNMENT_ID, SCORE, 'Graded' FROM GRADES;
+
+DROP TABLE GRADES;
+ALTER TABLE GRADES_NEW RENAME TO GRADES;
+
+CREATE TRIGGER CHECK_SCORE_MAX
+BEFORE INSERT ON GRADES
+FOR EACH ROW
+  WHEN NEW.SCORE > (SELECT MAXIMUM_SCORE FROM ASSIGNMENTS WHERE ID = NEW.ASSIGNMENT_ID)
+BEGIN
+  SELECT RAISE(ABORT, 'Score cannot exceed the assignment maximum score');
+END;
+
+CREATE TRIGGER CHECK_SCORE_MAX_UPDATE
+BEFORE UPDATE ON GRADES
+FOR EACH ROW
+WHEN NEW.SCORE > (SELECT MAXIMUM_SCORE FROM ASSIGNMENTS WHERE ID = NEW.ASSIGNMENT_ID)
+BEGIN
+  SELECT RAISE(ABORT, 'Score cannot exceed the assignment maximum score');
+END;
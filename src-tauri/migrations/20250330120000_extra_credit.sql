@@ -0,0 +1,26 @@
+ALTER TABLE ASSIGNMENTS ADD COLUMN ALLOWS_EXTRA_CREDIT INTEGER NOT NULL DEFAULT 0 CHECK (
+    ALLOWS_EXTRA_CREDIT IN (0, 1)
+);
+
+ALTER TABLE ASSIGNMENTS ADD COLUMN ASSIGNMENT_KIND TEXT NOT NULL DEFAULT 'Regular' CHECK (
+    ASSIGNMENT_KIND IN ('Regular', 'ExtraCredit')
+);
+
+DROP TRIGGER IF EXISTS CHECK_SCORE_MAX;
+DROP TRIGGER IF EXISTS CHECK_SCORE_MAX_UPDATE;
+
+CREATE TRIGGER CHECK_SCORE_MAX
+BEFORE INSERT ON GRADES
+FOR EACH ROW
+  WHEN NEW.SCORE > (SELECT MAXIMUM_SCORE FROM ASSIGNMENTS WHERE ID = NEW.ASSIGNMENT_ID AND ALLOWS_EXTRA_CREDIT = 0)
+BEGIN
+  SELECT RAISE(ABORT, 'Score cannot exceed the assignment maximum score');
+END;
+
+CREATE TRIGGER CHECK_SCORE_MAX_UPDATE
+BEFORE UPDATE ON GRADES
+FOR EACH ROW
+WHEN NEW.SCORE > (SELECT MAXIMUM_SCORE FROM ASSIGNMENTS WHERE ID = NEW.ASSIGNMENT_ID AND ALLOWS_EXTRA_CREDIT = 0)
+BEGIN
+  SELECT RAISE(ABORT, 'Score cannot exceed the assignment maximum score');
+END;
@@ -0,0 +1,21 @@
+ALTER TABLE GRADES ADD COLUMN SUBMITTED_AT TIMESTAMP;
+
+-- A policy without an assignment applies to the whole class, one with an
+-- assignment overrides the class policy for that assignment.
+CREATE TABLE IF NOT EXISTS LATE_POLICIES (
+    ID INTEGER PRIMARY KEY,
+    CLASS_ID INTEGER NOT NULL,
+    ASSIGNMENT_ID INTEGER,
+    PENALTY_PER_DAY REAL NOT NULL DEFAULT 0 CHECK (PENALTY_PER_DAY >= 0),
+    MAX_PENALTY REAL CHECK (MAX_PENALTY BETWEEN 0 AND 100),
+    GRACE_PERIOD_HOURS REAL NOT NULL DEFAULT 0 CHECK (GRACE_PERIOD_HOURS >= 0),
+    ZERO_AFTER_DAYS INTEGER CHECK (ZERO_AFTER_DAYS >= 0),
+    FOREIGN KEY (CLASS_ID) REFERENCES CLASSES (ID) ON DELETE CASCADE,
+    FOREIGN KEY (ASSIGNMENT_ID) REFERENCES ASSIGNMENTS (ID) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS IDX_LATE_POLICIES_CLASS
+ON LATE_POLICIES (CLASS_ID) WHERE ASSIGNMENT_ID IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS IDX_LATE_POLICIES_ASSIGNMENT
+ON LATE_POLICIES (ASSIGNMENT_ID) WHERE ASSIGNMENT_ID IS NOT NULL;
@@ -0,0 +1,18 @@
+-- An accommodation without a class applies to every class the student takes,
+-- one with a class overrides it for that class.
+CREATE TABLE IF NOT EXISTS STUDENT_ACCOMMODATIONS (
+    ID INTEGER PRIMARY KEY,
+    STUDENT_ID INTEGER NOT NULL,
+    CLASS_ID INTEGER,
+    EXTENSION_DAYS INTEGER NOT NULL DEFAULT 0 CHECK (EXTENSION_DAYS >= 0),
+    TIME_MULTIPLIER REAL NOT NULL DEFAULT 1 CHECK (TIME_MULTIPLIER >= 1),
+    NOTES TEXT,
+    FOREIGN KEY (STUDENT_ID) REFERENCES STUDENTS (ID) ON DELETE CASCADE,
+    FOREIGN KEY (CLASS_ID) REFERENCES CLASSES (ID) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS IDX_STUDENT_ACCOMMODATIONS_STUDENT
+ON STUDENT_ACCOMMODATIONS (STUDENT_ID) WHERE CLASS_ID IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS IDX_STUDENT_ACCOMMODATIONS_CLASS
+ON STUDENT_ACCOMMODATIONS (STUDENT_ID, CLASS_ID) WHERE CLASS_ID IS NOT NULL;
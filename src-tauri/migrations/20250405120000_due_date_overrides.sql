@@ -0,0 +1,11 @@
+-- A due date granted to one student for one assignment. It replaces both the
+-- assignment's due date and any accommodation the student has.
+CREATE TABLE IF NOT EXISTS DUE_DATE_OVERRIDES (
+    STUDENT_ID INTEGER NOT NULL,
+    ASSIGNMENT_ID INTEGER NOT NULL,
+    DUE_DATE TIMESTAMP NOT NULL,
+    REASON TEXT,
+    PRIMARY KEY (STUDENT_ID, ASSIGNMENT_ID),
+    FOREIGN KEY (STUDENT_ID) REFERENCES STUDENTS (ID) ON DELETE CASCADE,
+    FOREIGN KEY (ASSIGNMENT_ID) REFERENCES ASSIGNMENTS (ID) ON DELETE CASCADE
+);
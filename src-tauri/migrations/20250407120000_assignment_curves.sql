@@ -0,0 +1,11 @@
+-- The curve applied to an assignment's scores. Raw scores in GRADES are left
+-- as entered; deleting the row undoes the curve.
+CREATE TABLE IF NOT EXISTS ASSIGNMENT_CURVES (
+    ASSIGNMENT_ID INTEGER PRIMARY KEY,
+    CURVE_KIND TEXT NOT NULL CHECK (
+        CURVE_KIND IN ('FlatAdd', 'ScaleToHighest', 'SquareRoot', 'TargetMean')
+    ),
+    CURVE_VALUE REAL,
+    APPLIED_AT TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (ASSIGNMENT_ID) REFERENCES ASSIGNMENTS (ID) ON DELETE CASCADE
+);
@@ -0,0 +1,10 @@
+-- How often automatic backups are taken and how many are kept. The table only
+-- ever holds one row.
+CREATE TABLE IF NOT EXISTS BACKUP_SETTINGS (
+    ID INTEGER PRIMARY KEY CHECK (ID = 1),
+    INTERVAL_HOURS INTEGER NOT NULL DEFAULT 6 CHECK (INTERVAL_HOURS >= 0),
+    KEEP_DAILY INTEGER NOT NULL DEFAULT 7 CHECK (KEEP_DAILY >= 1),
+    KEEP_WEEKLY INTEGER NOT NULL DEFAULT 4 CHECK (KEEP_WEEKLY >= 0)
+);
+
+INSERT OR IGNORE INTO BACKUP_SETTINGS (ID) VALUES (1);
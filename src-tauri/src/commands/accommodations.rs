@@ -0,0 +1,180 @@
+use crate::{
+    database::models::{Assignment, DueDateOverride, StudentAccommodation, UpcomingDeadline},
+    error::GradifyError,
+    grading, validation, AppState,
+};
+use chrono::{Local, TimeDelta};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Longest extension an accommodation can grant.
+const MAX_EXTENSION_DAYS: i64 = 365;
+/// Furthest ahead upcoming deadlines can be listed.
+const MAX_WITHIN_DAYS: i64 = 365;
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn set_student_accommodation(
+    state: State<'_, Mutex<AppState>>,
+    student_id: i64,
+    class_id: Option<i64>,
+    extension_days: i64,
+    time_multiplier: f64,
+    notes: Option<String>,
+) -> Result<StudentAccommodation, GradifyError> {
+    let extension_days =
+        validation::whole_number_in("extension_days", extension_days, 0..=MAX_EXTENSION_DAYS)?;
+    let time_multiplier = validation::number_at_least("time_multiplier", time_multiplier, 1.0)?;
+    let state = state.lock().await;
+
+    let existing_id = sqlx::query_scalar::<_, i64>(
+        "SELECT ID FROM STUDENT_ACCOMMODATIONS WHERE STUDENT_ID = ? AND CLASS_ID IS ?",
+    )
+    .bind(student_id)
+    .bind(class_id)
+    .fetch_optional(&state.db.pool)
+    .await?;
+
+    let id = match existing_id {
+        Some(id) => {
+            sqlx::query(
+                "UPDATE STUDENT_ACCOMMODATIONS
+                 SET EXTENSION_DAYS = ?, TIME_MULTIPLIER = ?, NOTES = ?
+                 WHERE ID = ?",
+            )
+            .bind(extension_days)
+            .bind(time_multiplier)
+            .bind(&notes)
+            .bind(id)
+            .execute(&state.db.pool)
+            .await?;
+            id
+        }
+        None => sqlx::query(
+            "INSERT INTO STUDENT_ACCOMMODATIONS
+                 (STUDENT_ID, CLASS_ID, EXTENSION_DAYS, TIME_MULTIPLIER, NOTES)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(student_id)
+        .bind(class_id)
+        .bind(extension_days)
+        .bind(time_multiplier)
+        .bind(&notes)
+        .execute(&state.db.pool)
+        .await?
+        .last_insert_rowid(),
+    };
+
+    let accommodation = sqlx::query_as::<_, StudentAccommodation>(
+        "SELECT ID, STUDENT_ID, CLASS_ID, EXTENSION_DAYS, TIME_MULTIPLIER, NOTES
+         FROM STUDENT_ACCOMMODATIONS
+         WHERE ID = ?",
+    )
+    .bind(id)
+    .fetch_one(&state.db.pool)
+    .await?;
+
+    Ok(accommodation)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_student_accommodations(
+    state: State<'_, Mutex<AppState>>,
+    student_id: i64,
+) -> Result<Vec<StudentAccommodation>, GradifyError> {
+    let state = state.lock().await;
+    let accommodations = sqlx::query_as::<_, StudentAccommodation>(
+        "SELECT ID, STUDENT_ID, CLASS_ID, EXTENSION_DAYS, TIME_MULTIPLIER, NOTES
+         FROM STUDENT_ACCOMMODATIONS
+         WHERE STUDENT_ID = ?",
+    )
+    .bind(student_id)
+    .fetch_all(&state.db.pool)
+    .await?;
+    Ok(accommodations)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn delete_student_accommodation(
+    state: State<'_, Mutex<AppState>>,
+    id: i64,
+) -> Result<(), GradifyError> {
+    let state = state.lock().await;
+    sqlx::query("DELETE FROM STUDENT_ACCOMMODATIONS WHERE ID = ?")
+        .bind(id)
+        .execute(&state.db.pool)
+        .await?;
+    Ok(())
+}
+
+/// Assignments of the student's classes still ahead of their effective due
+/// date, soonest first. Assignments without a due date are only included when
+/// the student has an override for them. `within_days`, at most a year, limits
+/// how far ahead to look.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_upcoming_deadlines(
+    state: State<'_, Mutex<AppState>>,
+    student_id: i64,
+    within_days: Option<i64>,
+) -> Result<Vec<UpcomingDeadline>, GradifyError> {
+    let within_days = within_days
+        .map(|days| validation::whole_number_in("within_days", days, 1..=MAX_WITHIN_DAYS))
+        .transpose()?;
+    let state = state.lock().await;
+
+    let assignments = sqlx::query_as::<_, Assignment>(
+        "SELECT a.ID, a.CLASS_ID, a.ASSIGNMENT_NAME, a.CATEGORY_ID, c.CATEGORY_NAME, a.MAXIMUM_SCORE,
+                a.DUE_DATE, a.ALLOWS_EXTRA_CREDIT, a.ASSIGNMENT_KIND
+         FROM ASSIGNMENTS a
+         JOIN CATEGORIES c ON c.ID = a.CATEGORY_ID
+         JOIN STUDENT_CLASSES sc ON sc.CLASS_ID = a.CLASS_ID
+         WHERE sc.STUDENT_ID = ?",
+    )
+    .bind(student_id)
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    let accommodations = sqlx::query_as::<_, StudentAccommodation>(
+        "SELECT ID, STUDENT_ID, CLASS_ID, EXTENSION_DAYS, TIME_MULTIPLIER, NOTES
+         FROM STUDENT_ACCOMMODATIONS
+         WHERE STUDENT_ID = ?",
+    )
+    .bind(student_id)
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    let overrides = sqlx::query_as::<_, DueDateOverride>(
+        "SELECT STUDENT_ID, ASSIGNMENT_ID, DUE_DATE, REASON
+         FROM DUE_DATE_OVERRIDES
+         WHERE STUDENT_ID = ?",
+    )
+    .bind(student_id)
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    let now = Local::now().naive_local();
+    let horizon = within_days
+        .and_then(TimeDelta::try_days)
+        .and_then(|within| now.checked_add_signed(within));
+
+    let mut deadlines: Vec<UpcomingDeadline> = assignments
+        .into_iter()
+        .filter_map(|assignment| {
+            let effective_due_date =
+                grading::effective_due_date(&assignment, student_id, &overrides, &accommodations)?;
+            if effective_due_date < now || horizon.is_some_and(|h| effective_due_date > h) {
+                return None;
+            }
+            Some(UpcomingDeadline {
+                student_id,
+                assignment_id: assignment.id,
+                assignment_name: assignment.assignment_name,
+                class_id: assignment.class_id,
+                due_date: assignment.due_date,
+                effective_due_date,
+            })
+        })
+        .collect();
+    deadlines.sort_by_key(|d| d.effective_due_date);
+
+    Ok(deadlines)
+}
@@ -0,0 +1,74 @@
+use crate::{
+    commands::backups::replace_current_database,
+    database::{archive, db::Database, location, models::ArchiveManifest},
+    error::GradifyError,
+    AppState, StartupState,
+};
+use std::path::PathBuf;
+use tauri::State;
+use tokio::{fs, sync::Mutex};
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn export_gradify_archive(
+    state: State<'_, Mutex<AppState>>,
+    archive_path: PathBuf,
+) -> Result<ArchiveManifest, GradifyError> {
+    let state = state.lock().await;
+    archive::export_archive(&state.db.pool, &archive_path).await
+}
+
+/// Imports a `.gradify` archive. Without a `profile_path` it replaces the
+/// current data, which is backed up first. With one, the archive becomes a new
+/// database at that path and the app switches to it, leaving the current
+/// database file alone. The switch is remembered, so later starts open the new
+/// database too unless `--db-path` or `GRADIFY_DB_PATH` say otherwise. Either
+/// way the imported database is upgraded to the current schema.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn import_gradify_archive(
+    state: State<'_, Mutex<AppState>>,
+    startup: State<'_, Mutex<StartupState>>,
+    archive_path: PathBuf,
+    profile_path: Option<PathBuf>,
+) -> Result<ArchiveManifest, GradifyError> {
+    let (manifest, database) = archive::read_archive(&archive_path)?;
+
+    if let Some(profile_path) = profile_path {
+        let profile_path = std::path::absolute(&profile_path)?;
+        if profile_path.exists() {
+            return Err(GradifyError::validation(
+                "profile_path",
+                format!("{} already exists", profile_path.display()),
+            ));
+        }
+        if let Some(dir) = profile_path.parent() {
+            fs::create_dir_all(dir).await?;
+        }
+        fs::write(&profile_path, &database).await?;
+        let profile = match Database::open(&profile_path).await {
+            Ok(profile) => profile,
+            Err(e) => {
+                fs::remove_file(&profile_path).await?;
+                return Err(e);
+            }
+        };
+
+        let mut startup = startup.lock().await;
+        location::save_database_path(&startup.app_data_dir, &profile_path)?;
+        let mut state = state.lock().await;
+        state.db.pool.close().await;
+        state.db = profile;
+        startup.db_path = profile_path;
+        return Ok(manifest);
+    }
+
+    let imported_path = archive::temp_path("import");
+    fs::write(&imported_path, &database).await?;
+
+    let mut startup = startup.lock().await;
+    let mut state = state.lock().await;
+    let replaced = replace_current_database(&mut state, &mut startup, &imported_path).await;
+    fs::remove_file(&imported_path).await?;
+    replaced?;
+
+    Ok(manifest)
+}
@@ -1,43 +1,55 @@
-use crate::{database::models::Assignment, AppState};
+use crate::{
+    database::models::{Assignment, AssignmentKind},
+    error::GradifyError,
+    validation, AppState,
+};
 use chrono::NaiveDateTime;
 use tauri::State;
 use tokio::sync::Mutex;
 
 #[tauri::command(async, rename_all = "snake_case")]
+#[allow(clippy::too_many_arguments)]
 pub async fn create_assignment(
     state: State<'_, Mutex<AppState>>,
     class_id: i64,
     assignment_name: String,
-    assignment_type: String,
+    category_id: i64,
     maximum_score: f64,
     due_date: Option<NaiveDateTime>,
-) -> Result<Assignment, String> {
+    allows_extra_credit: bool,
+    assignment_kind: AssignmentKind,
+) -> Result<Assignment, GradifyError> {
+    let assignment_name = validation::required_text("assignment_name", &assignment_name)?;
+    let maximum_score = validation::positive_number("maximum_score", maximum_score)?;
     let state = state.lock().await;
 
     let result = sqlx::query(
-        "INSERT INTO ASSIGNMENTS (CLASS_ID, ASSIGNMENT_NAME, ASSIGNMENT_TYPE, MAXIMUM_SCORE, DUE_DATE)
-         VALUES (?, ?, ?, ?, ?)"
+        "INSERT INTO ASSIGNMENTS (CLASS_ID, ASSIGNMENT_NAME, CATEGORY_ID, MAXIMUM_SCORE, DUE_DATE,
+                                  ALLOWS_EXTRA_CREDIT, ASSIGNMENT_KIND)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(class_id)
     .bind(&assignment_name)
-    .bind(&assignment_type)
+    .bind(category_id)
     .bind(maximum_score)
     .bind(due_date)
+    .bind(allows_extra_credit)
+    .bind(assignment_kind)
     .execute(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
     let id = result.last_insert_rowid();
 
     let assignment = sqlx::query_as::<_, Assignment>(
-        "SELECT ID, CLASS_ID, ASSIGNMENT_NAME, ASSIGNMENT_TYPE, MAXIMUM_SCORE, DUE_DATE 
-         FROM ASSIGNMENTS
-         WHERE ID = ?",
+        "SELECT a.ID, a.CLASS_ID, a.ASSIGNMENT_NAME, a.CATEGORY_ID, c.CATEGORY_NAME, a.MAXIMUM_SCORE,
+                a.DUE_DATE, a.ALLOWS_EXTRA_CREDIT, a.ASSIGNMENT_KIND
+         FROM ASSIGNMENTS a
+         JOIN CATEGORIES c ON c.ID = a.CATEGORY_ID
+         WHERE a.ID = ?",
     )
     .bind(id)
     .fetch_one(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
     Ok(assignment)
 }
@@ -46,82 +58,98 @@ pub async fn create_assignment(
 pub async fn get_assignment(
     state: State<'_, Mutex<AppState>>,
     id: i64,
-) -> Result<Assignment, String> {
+) -> Result<Assignment, GradifyError> {
     let state = state.lock().await;
     let assignment = sqlx::query_as::<_, Assignment>(
-        "SELECT ID, CLASS_ID, ASSIGNMENT_NAME, ASSIGNMENT_TYPE, MAXIMUM_SCORE, DUE_DATE 
-         FROM ASSIGNMENTS
-         WHERE ID = ?",
+        "SELECT a.ID, a.CLASS_ID, a.ASSIGNMENT_NAME, a.CATEGORY_ID, c.CATEGORY_NAME, a.MAXIMUM_SCORE,
+                a.DUE_DATE, a.ALLOWS_EXTRA_CREDIT, a.ASSIGNMENT_KIND
+         FROM ASSIGNMENTS a
+         JOIN CATEGORIES c ON c.ID = a.CATEGORY_ID
+         WHERE a.ID = ?",
     )
     .bind(id)
     .fetch_one(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
     Ok(assignment)
 }
 
 #[tauri::command(async, rename_all = "snake_case")]
 pub async fn get_all_assignments(
     state: State<'_, Mutex<AppState>>,
-) -> Result<Vec<Assignment>, String> {
+) -> Result<Vec<Assignment>, GradifyError> {
     let state = state.lock().await;
     let assignments = sqlx::query_as::<_, Assignment>(
-        "SELECT ID, CLASS_ID, ASSIGNMENT_NAME, ASSIGNMENT_TYPE, MAXIMUM_SCORE, DUE_DATE 
-         FROM ASSIGNMENTS",
+        "SELECT a.ID, a.CLASS_ID, a.ASSIGNMENT_NAME, a.CATEGORY_ID, c.CATEGORY_NAME, a.MAXIMUM_SCORE,
+                a.DUE_DATE, a.ALLOWS_EXTRA_CREDIT, a.ASSIGNMENT_KIND
+         FROM ASSIGNMENTS a
+         JOIN CATEGORIES c ON c.ID = a.CATEGORY_ID",
     )
     .fetch_all(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
     Ok(assignments)
 }
 
 #[tauri::command(async, rename_all = "snake_case")]
+#[allow(clippy::too_many_arguments)]
 pub async fn update_assignment(
     state: State<'_, Mutex<AppState>>,
     id: i64,
     class_id: i64,
     assignment_name: String,
-    assignment_type: String,
+    category_id: i64,
     maximum_score: f64,
     due_date: Option<NaiveDateTime>,
-) -> Result<Assignment, String> {
+    allows_extra_credit: bool,
+    assignment_kind: AssignmentKind,
+) -> Result<Assignment, GradifyError> {
+    let assignment_name = validation::required_text("assignment_name", &assignment_name)?;
+    let maximum_score = validation::positive_number("maximum_score", maximum_score)?;
     let state = state.lock().await;
 
-    sqlx::query(
+    let result = sqlx::query(
         "UPDATE ASSIGNMENTS
-         SET CLASS_ID = ?, ASSIGNMENT_NAME = ?, ASSIGNMENT_TYPE = ?, MAXIMUM_SCORE = ?, DUE_DATE = ?
+         SET CLASS_ID = ?, ASSIGNMENT_NAME = ?, CATEGORY_ID = ?, MAXIMUM_SCORE = ?, DUE_DATE = ?,
+             ALLOWS_EXTRA_CREDIT = ?, ASSIGNMENT_KIND = ?
          WHERE ID = ?",
     )
     .bind(class_id)
     .bind(&assignment_name)
-    .bind(&assignment_type)
+    .bind(category_id)
     .bind(maximum_score)
     .bind(due_date)
+    .bind(allows_extra_credit)
+    .bind(assignment_kind)
     .bind(id)
     .execute(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(GradifyError::not_found(format!("Assignment {id}")));
+    }
 
     let assignment = sqlx::query_as::<_, Assignment>(
-        "SELECT ID, CLASS_ID, ASSIGNMENT_NAME, ASSIGNMENT_TYPE, MAXIMUM_SCORE, DUE_DATE 
-         FROM ASSIGNMENTS
-         WHERE ID = ?",
+        "SELECT a.ID, a.CLASS_ID, a.ASSIGNMENT_NAME, a.CATEGORY_ID, c.CATEGORY_NAME, a.MAXIMUM_SCORE,
+                a.DUE_DATE, a.ALLOWS_EXTRA_CREDIT, a.ASSIGNMENT_KIND
+         FROM ASSIGNMENTS a
+         JOIN CATEGORIES c ON c.ID = a.CATEGORY_ID
+         WHERE a.ID = ?",
     )
     .bind(id)
     .fetch_one(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
     Ok(assignment)
 }
 
 #[tauri::command(async, rename_all = "snake_case")]
-pub async fn delete_assignment(state: State<'_, Mutex<AppState>>, id: i64) -> Result<(), String> {
+pub async fn delete_assignment(
+    state: State<'_, Mutex<AppState>>,
+    id: i64,
+) -> Result<(), GradifyError> {
     let state = state.lock().await;
     sqlx::query("DELETE FROM ASSIGNMENTS WHERE ID = ?")
         .bind(id)
         .execute(&state.db.pool)
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
     Ok(())
 }
@@ -0,0 +1,225 @@
+use crate::{
+    database::{
+        backup,
+        db::Database,
+        location,
+        models::{BackupInfo, BackupReason, BackupSettings},
+    },
+    error::GradifyError,
+    validation, AppState, StartupState,
+};
+use chrono::{Local, TimeDelta};
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+use tokio::{fs, sync::Mutex};
+
+/// How often the schedule checks whether a backup is due.
+const SCHEDULE_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+/// Longest interval between scheduled backups, a year.
+const MAX_INTERVAL_HOURS: i64 = 24 * 365;
+
+async fn load_backup_settings(pool: &SqlitePool) -> Result<BackupSettings, sqlx::Error> {
+    sqlx::query_as::<_, BackupSettings>(
+        "SELECT INTERVAL_HOURS, KEEP_DAILY, KEEP_WEEKLY FROM BACKUP_SETTINGS WHERE ID = 1",
+    )
+    .fetch_one(pool)
+    .await
+}
+
+async fn backup_dir(startup: &Mutex<StartupState>) -> PathBuf {
+    backup::backup_dir(&startup.lock().await.db_path)
+}
+
+/// Takes a backup and prunes the startup and scheduled backups the retention
+/// rules no longer keep.
+pub(crate) async fn automatic_backup(
+    pool: &SqlitePool,
+    dir: &Path,
+    reason: BackupReason,
+) -> Result<BackupInfo, GradifyError> {
+    let backup = backup::create_backup(pool, dir, reason).await?;
+    let settings = load_backup_settings(pool).await?;
+    backup::prune_backups(dir, &settings)?;
+    Ok(backup)
+}
+
+/// Takes a scheduled backup whenever the newest automatic backup is older than
+/// the configured interval. Runs for as long as the app does. A failure is
+/// kept in `StartupState` for the UI until a later backup succeeds.
+pub(crate) async fn run_backup_schedule(app: AppHandle) {
+    loop {
+        tokio::time::sleep(SCHEDULE_CHECK_INTERVAL).await;
+
+        // Nothing to back up while the app is in recovery mode.
+        let Some(state) = app.try_state::<Mutex<AppState>>() else {
+            continue;
+        };
+        let startup = app.state::<Mutex<StartupState>>();
+        let dir = {
+            let startup = startup.lock().await;
+            if startup.error.is_some() {
+                continue;
+            }
+            backup::backup_dir(&startup.db_path)
+        };
+        let scheduled = scheduled_backup(&state.lock().await.db.pool, &dir).await;
+
+        let mut startup = startup.lock().await;
+        match scheduled {
+            Ok(Some(_)) => startup.backup_error = None,
+            Ok(None) => {}
+            Err(e) => startup.backup_error = Some(format!("Scheduled backup failed: {e}")),
+        }
+    }
+}
+
+/// Takes a scheduled backup if one is due, returning it.
+async fn scheduled_backup(
+    pool: &SqlitePool,
+    dir: &Path,
+) -> Result<Option<BackupInfo>, GradifyError> {
+    let settings = load_backup_settings(pool).await?;
+    if settings.interval_hours == 0 {
+        return Ok(None);
+    }
+
+    let last_backup = backup::list_backups(dir)?
+        .into_iter()
+        .find(|backup| backup.reason != BackupReason::Manual)
+        .map(|backup| backup.created_at);
+    let due = match last_backup {
+        Some(created_at) => TimeDelta::try_hours(settings.interval_hours)
+            .is_some_and(|interval| Local::now().naive_local() - created_at >= interval),
+        None => true,
+    };
+    if !due {
+        return Ok(None);
+    }
+    Ok(Some(
+        automatic_backup(pool, dir, BackupReason::Scheduled).await?,
+    ))
+}
+
+/// Why the last automatic backup failed, or `None` when it succeeded.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_backup_error(
+    startup: State<'_, Mutex<StartupState>>,
+) -> Result<Option<String>, GradifyError> {
+    let startup = startup.lock().await;
+    Ok(startup.backup_error.clone())
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn list_backups(
+    startup: State<'_, Mutex<StartupState>>,
+) -> Result<Vec<BackupInfo>, GradifyError> {
+    let dir = backup_dir(&startup).await;
+    Ok(backup::list_backups(&dir)?)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn create_backup(
+    state: State<'_, Mutex<AppState>>,
+    startup: State<'_, Mutex<StartupState>>,
+) -> Result<BackupInfo, GradifyError> {
+    let dir = backup_dir(&startup).await;
+    let state = state.lock().await;
+    backup::create_backup(&state.db.pool, &dir, BackupReason::Manual).await
+}
+
+/// Replaces the database with the backup named `file_name`.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn restore_backup(
+    state: State<'_, Mutex<AppState>>,
+    startup: State<'_, Mutex<StartupState>>,
+    file_name: String,
+) -> Result<(), GradifyError> {
+    let mut startup = startup.lock().await;
+    let dir = backup::backup_dir(&startup.db_path);
+    let backup = backup::list_backups(&dir)?
+        .into_iter()
+        .find(|backup| backup.file_name == file_name)
+        .ok_or_else(|| GradifyError::not_found(format!("Backup {file_name}")))?;
+
+    let mut state = state.lock().await;
+    replace_current_database(&mut state, &mut startup, Path::new(&backup.path)).await
+}
+
+/// Replaces the open database with a copy of the database at `source`. The
+/// current database is backed up first, and put back if `source` cannot be
+/// opened. Should that fail too, the app falls back to recovery mode, from
+/// which the backup can be restored.
+pub(crate) async fn replace_current_database(
+    state: &mut AppState,
+    startup: &mut StartupState,
+    source: &Path,
+) -> Result<(), GradifyError> {
+    let db_path = startup.db_path.clone();
+    let dir = backup::backup_dir(&db_path);
+    // Not pruned here, as pruning could remove the backup being restored.
+    let current = backup::create_backup(&state.db.pool, &dir, BackupReason::Restore).await?;
+    state.db.pool.close().await;
+
+    let e = match replace_database(&db_path, source).await {
+        Ok(db) => {
+            state.db = db;
+            return Ok(());
+        }
+        Err(e) => e,
+    };
+    match replace_database(&db_path, Path::new(&current.path)).await {
+        Ok(db) => state.db = db,
+        Err(reopen_error) => startup.error = Some(reopen_error.to_string()),
+    }
+    Err(e)
+}
+
+async fn replace_database(db_path: &Path, source: &Path) -> Result<Database, GradifyError> {
+    location::remove_journals(db_path)?;
+    fs::copy(source, db_path).await?;
+    Database::open(db_path).await
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_backup_settings(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<BackupSettings, GradifyError> {
+    let state = state.lock().await;
+    Ok(load_backup_settings(&state.db.pool).await?)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn update_backup_settings(
+    state: State<'_, Mutex<AppState>>,
+    interval_hours: i64,
+    keep_daily: i64,
+    keep_weekly: i64,
+) -> Result<BackupSettings, GradifyError> {
+    let interval_hours =
+        validation::whole_number_in("interval_hours", interval_hours, 0..=MAX_INTERVAL_HOURS)?;
+    if keep_daily < 1 {
+        return Err(GradifyError::validation(
+            "keep_daily",
+            "At least one daily backup has to be kept",
+        ));
+    }
+    if keep_weekly < 0 {
+        return Err(GradifyError::validation(
+            "keep_weekly",
+            "Weekly backups to keep cannot be negative",
+        ));
+    }
+
+    let state = state.lock().await;
+    sqlx::query(
+        "UPDATE BACKUP_SETTINGS SET INTERVAL_HOURS = ?, KEEP_DAILY = ?, KEEP_WEEKLY = ? WHERE ID = 1",
+    )
+    .bind(interval_hours)
+    .bind(keep_daily)
+    .bind(keep_weekly)
+    .execute(&state.db.pool)
+    .await?;
+
+    Ok(load_backup_settings(&state.db.pool).await?)
+}
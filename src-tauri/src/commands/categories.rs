@@ -0,0 +1,238 @@
+use crate::{database::models::Category, error::GradifyError, validation, AppState};
+use sqlx::SqlitePool;
+use tauri::State;
+use tokio::sync::Mutex;
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn create_category(
+    state: State<'_, Mutex<AppState>>,
+    class_id: i64,
+    category_name: String,
+    weight: Option<f64>,
+    drop_lowest: i64,
+    drop_highest: i64,
+) -> Result<Category, GradifyError> {
+    let state = state.lock().await;
+    insert_category(
+        &state.db.pool,
+        class_id,
+        &category_name,
+        weight,
+        drop_lowest,
+        drop_highest,
+    )
+    .await
+}
+
+async fn insert_category(
+    pool: &SqlitePool,
+    class_id: i64,
+    category_name: &str,
+    weight: Option<f64>,
+    drop_lowest: i64,
+    drop_highest: i64,
+) -> Result<Category, GradifyError> {
+    let category_name = validation::required_text("category_name", category_name)?;
+
+    let result = sqlx::query(
+        "INSERT INTO CATEGORIES (CLASS_ID, CATEGORY_NAME, WEIGHT, DROP_LOWEST, DROP_HIGHEST)
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(class_id)
+    .bind(&category_name)
+    .bind(weight)
+    .bind(drop_lowest)
+    .bind(drop_highest)
+    .execute(pool)
+    .await?;
+
+    let id = result.last_insert_rowid();
+
+    let category = sqlx::query_as::<_, Category>(
+        "SELECT ID, CLASS_ID, CATEGORY_NAME, WEIGHT, DROP_LOWEST, DROP_HIGHEST FROM CATEGORIES WHERE ID = ?",
+    )
+    .bind(id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(category)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_category(
+    state: State<'_, Mutex<AppState>>,
+    id: i64,
+) -> Result<Category, GradifyError> {
+    let state = state.lock().await;
+    let category = sqlx::query_as::<_, Category>(
+        "SELECT ID, CLASS_ID, CATEGORY_NAME, WEIGHT, DROP_LOWEST, DROP_HIGHEST FROM CATEGORIES WHERE ID = ?",
+    )
+    .bind(id)
+    .fetch_one(&state.db.pool)
+    .await?;
+    Ok(category)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_categories(
+    state: State<'_, Mutex<AppState>>,
+    class_id: i64,
+) -> Result<Vec<Category>, GradifyError> {
+    let state = state.lock().await;
+    let categories = sqlx::query_as::<_, Category>(
+        "SELECT ID, CLASS_ID, CATEGORY_NAME, WEIGHT, DROP_LOWEST, DROP_HIGHEST FROM CATEGORIES WHERE CLASS_ID = ?",
+    )
+    .bind(class_id)
+    .fetch_all(&state.db.pool)
+    .await?;
+    Ok(categories)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_all_categories(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Vec<Category>, GradifyError> {
+    let state = state.lock().await;
+    let categories = sqlx::query_as::<_, Category>(
+        "SELECT ID, CLASS_ID, CATEGORY_NAME, WEIGHT, DROP_LOWEST, DROP_HIGHEST FROM CATEGORIES",
+    )
+    .fetch_all(&state.db.pool)
+    .await?;
+    Ok(categories)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn update_category(
+    state: State<'_, Mutex<AppState>>,
+    id: i64,
+    category_name: String,
+    weight: Option<f64>,
+    drop_lowest: i64,
+    drop_highest: i64,
+) -> Result<Category, GradifyError> {
+    let state = state.lock().await;
+    save_category(
+        &state.db.pool,
+        id,
+        &category_name,
+        weight,
+        drop_lowest,
+        drop_highest,
+    )
+    .await
+}
+
+async fn save_category(
+    pool: &SqlitePool,
+    id: i64,
+    category_name: &str,
+    weight: Option<f64>,
+    drop_lowest: i64,
+    drop_highest: i64,
+) -> Result<Category, GradifyError> {
+    let category_name = validation::required_text("category_name", category_name)?;
+
+    let result = sqlx::query(
+        "UPDATE CATEGORIES
+         SET CATEGORY_NAME = ?, WEIGHT = ?, DROP_LOWEST = ?, DROP_HIGHEST = ?
+         WHERE ID = ?",
+    )
+    .bind(&category_name)
+    .bind(weight)
+    .bind(drop_lowest)
+    .bind(drop_highest)
+    .bind(id)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(GradifyError::not_found(format!("Category {id}")));
+    }
+
+    let category = sqlx::query_as::<_, Category>(
+        "SELECT ID, CLASS_ID, CATEGORY_NAME, WEIGHT, DROP_LOWEST, DROP_HIGHEST FROM CATEGORIES WHERE ID = ?",
+    )
+    .bind(id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(category)
+}
+
+/// Deletes a category, which has to have no assignments left.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn delete_category(
+    state: State<'_, Mutex<AppState>>,
+    id: i64,
+) -> Result<(), GradifyError> {
+    let state = state.lock().await;
+
+    let has_assignments = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS (SELECT 1 FROM ASSIGNMENTS WHERE CATEGORY_ID = ?)",
+    )
+    .bind(id)
+    .fetch_one(&state.db.pool)
+    .await?;
+    if has_assignments {
+        return Err(GradifyError::validation(
+            "category_id",
+            "The category still has assignments; move or delete them first",
+        ));
+    }
+
+    sqlx::query("DELETE FROM CATEGORIES WHERE ID = ?")
+        .bind(id)
+        .execute(&state.db.pool)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::db::Database;
+
+    async fn database(name: &str) -> Database {
+        let dir = std::env::temp_dir().join(format!("gradify-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        Database::open(&dir.join("db.sqlite")).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn blank_category_names_are_rejected() {
+        let db = database("blank-category").await;
+        let class_id = sqlx::query("INSERT INTO CLASSES (CLASS_NAME) VALUES ('Math')")
+            .execute(&db.pool)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+
+        let error = insert_category(&db.pool, class_id, "   ", None, 0, 0)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            GradifyError::Validation { ref field, .. } if field == "category_name"
+        ));
+        let category = insert_category(&db.pool, class_id, " Quizzes ", None, 0, 0)
+            .await
+            .unwrap();
+        assert_eq!(category.category_name, "Quizzes");
+        let error = save_category(&db.pool, category.id, "", None, 0, 0)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, GradifyError::Validation { .. }));
+    }
+
+    #[tokio::test]
+    async fn updating_a_missing_category_is_not_found() {
+        let db = database("missing-category").await;
+
+        let error = save_category(&db.pool, 999, "Quizzes", None, 0, 0)
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.to_string(), "Category 999 not found");
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{database::models::Class, AppState};
+use crate::{
+    database::models::{Class, GradingMode},
+    error::GradifyError,
+    validation, AppState,
+};
 use tauri::State;
 use tokio::sync::Mutex;
 
@@ -7,7 +11,9 @@ pub async fn create_class(
     state: State<'_, Mutex<AppState>>,
     class_name: String,
     description: Option<String>,
-) -> Result<Class, String> {
+) -> Result<Class, GradifyError> {
+    let class_name = validation::required_text("class_name", &class_name)?;
+    let description = validation::optional_text(description.as_deref());
     let state = state.lock().await;
 
     let result = sqlx::query(
@@ -17,40 +23,42 @@ pub async fn create_class(
     .bind(&class_name)
     .bind(&description)
     .execute(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
     let id = result.last_insert_rowid();
 
-    let class =
-        sqlx::query_as::<_, Class>("SELECT ID, CLASS_NAME, DESCRIPTION FROM CLASSES WHERE ID = ?")
-            .bind(id)
-            .fetch_one(&state.db.pool)
-            .await
-            .map_err(|e| e.to_string())?;
+    let class = sqlx::query_as::<_, Class>(
+        "SELECT ID, CLASS_NAME, DESCRIPTION, GRADING_MODE, GRADING_SCALE_ID FROM CLASSES WHERE ID = ?",
+    )
+    .bind(id)
+    .fetch_one(&state.db.pool)
+    .await?;
 
     Ok(class)
 }
 
 #[tauri::command(async, rename_all = "snake_case")]
-pub async fn get_class(state: State<'_, Mutex<AppState>>, id: i64) -> Result<Class, String> {
+pub async fn get_class(state: State<'_, Mutex<AppState>>, id: i64) -> Result<Class, GradifyError> {
     let state = state.lock().await;
-    let class =
-        sqlx::query_as::<_, Class>("SELECT ID, CLASS_NAME, DESCRIPTION FROM CLASSES WHERE ID = ?")
-            .bind(id)
-            .fetch_one(&state.db.pool)
-            .await
-            .map_err(|e| e.to_string())?;
+    let class = sqlx::query_as::<_, Class>(
+        "SELECT ID, CLASS_NAME, DESCRIPTION, GRADING_MODE, GRADING_SCALE_ID FROM CLASSES WHERE ID = ?",
+    )
+    .bind(id)
+    .fetch_one(&state.db.pool)
+    .await?;
     Ok(class)
 }
 
 #[tauri::command(async, rename_all = "snake_case")]
-pub async fn get_all_classes(state: State<'_, Mutex<AppState>>) -> Result<Vec<Class>, String> {
+pub async fn get_all_classes(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Vec<Class>, GradifyError> {
     let state = state.lock().await;
-    let classes = sqlx::query_as::<_, Class>("SELECT ID, CLASS_NAME, DESCRIPTION FROM CLASSES")
-        .fetch_all(&state.db.pool)
-        .await
-        .map_err(|e| e.to_string())?;
+    let classes = sqlx::query_as::<_, Class>(
+        "SELECT ID, CLASS_NAME, DESCRIPTION, GRADING_MODE, GRADING_SCALE_ID FROM CLASSES",
+    )
+    .fetch_all(&state.db.pool)
+    .await?;
     Ok(classes)
 }
 
@@ -60,10 +68,12 @@ pub async fn update_class(
     id: i64,
     class_name: String,
     description: Option<String>,
-) -> Result<Class, String> {
+) -> Result<Class, GradifyError> {
+    let class_name = validation::required_text("class_name", &class_name)?;
+    let description = validation::optional_text(description.as_deref());
     let state = state.lock().await;
 
-    sqlx::query(
+    let result = sqlx::query(
         "UPDATE CLASSES
          SET CLASS_NAME = ?, DESCRIPTION = ?
          WHERE ID = ?",
@@ -72,26 +82,92 @@ pub async fn update_class(
     .bind(&description)
     .bind(id)
     .execute(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(GradifyError::not_found(format!("Class {id}")));
+    }
+
+    let class = sqlx::query_as::<_, Class>(
+        "SELECT ID, CLASS_NAME, DESCRIPTION, GRADING_MODE, GRADING_SCALE_ID FROM CLASSES WHERE ID = ?",
+    )
+    .bind(id)
+    .fetch_one(&state.db.pool)
+    .await?;
+
+    Ok(class)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn set_class_grading_mode(
+    state: State<'_, Mutex<AppState>>,
+    id: i64,
+    grading_mode: GradingMode,
+) -> Result<Class, GradifyError> {
+    let state = state.lock().await;
+
+    let result = sqlx::query(
+        "UPDATE CLASSES
+         SET GRADING_MODE = ?
+         WHERE ID = ?",
+    )
+    .bind(grading_mode)
+    .bind(id)
+    .execute(&state.db.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(GradifyError::not_found(format!("Class {id}")));
+    }
+
+    let class = sqlx::query_as::<_, Class>(
+        "SELECT ID, CLASS_NAME, DESCRIPTION, GRADING_MODE, GRADING_SCALE_ID FROM CLASSES WHERE ID = ?",
+    )
+    .bind(id)
+    .fetch_one(&state.db.pool)
+    .await?;
+
+    Ok(class)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn set_class_grading_scale(
+    state: State<'_, Mutex<AppState>>,
+    id: i64,
+    grading_scale_id: Option<i64>,
+) -> Result<Class, GradifyError> {
+    let state = state.lock().await;
+
+    let result = sqlx::query(
+        "UPDATE CLASSES
+         SET GRADING_SCALE_ID = ?
+         WHERE ID = ?",
+    )
+    .bind(grading_scale_id)
+    .bind(id)
+    .execute(&state.db.pool)
+    .await?;
 
-    let class =
-        sqlx::query_as::<_, Class>("SELECT ID, CLASS_NAME, DESCRIPTION FROM CLASSES WHERE ID = ?")
-            .bind(id)
-            .fetch_one(&state.db.pool)
-            .await
-            .map_err(|e| e.to_string())?;
+    if result.rows_affected() == 0 {
+        return Err(GradifyError::not_found(format!("Class {id}")));
+    }
+
+    let class = sqlx::query_as::<_, Class>(
+        "SELECT ID, CLASS_NAME, DESCRIPTION, GRADING_MODE, GRADING_SCALE_ID FROM CLASSES WHERE ID = ?",
+    )
+    .bind(id)
+    .fetch_one(&state.db.pool)
+    .await?;
 
     Ok(class)
 }
 
 #[tauri::command(async, rename_all = "snake_case")]
-pub async fn delete_class(state: State<'_, Mutex<AppState>>, id: i64) -> Result<(), String> {
+pub async fn delete_class(state: State<'_, Mutex<AppState>>, id: i64) -> Result<(), GradifyError> {
     let state = state.lock().await;
     sqlx::query("DELETE FROM CLASSES WHERE ID = ?")
         .bind(id)
         .execute(&state.db.pool)
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
     Ok(())
 }
@@ -0,0 +1,133 @@
+use crate::{
+    commands::overall_grades::load_assignment,
+    database::models::{Assignment, AssignmentCurve, CurveKind, CurvedScore, Grade},
+    error::GradifyError,
+    grading, AppState,
+};
+use sqlx::SqlitePool;
+use tauri::State;
+use tokio::sync::Mutex;
+
+fn validate_curve(curve_kind: CurveKind, curve_value: Option<f64>) -> Result<(), GradifyError> {
+    match (curve_kind, curve_value) {
+        (CurveKind::FlatAdd | CurveKind::TargetMean, None) => Err(GradifyError::validation(
+            "curve_value",
+            format!("A {curve_kind:?} curve needs a value"),
+        )),
+        (_, Some(value)) if !value.is_finite() => Err(GradifyError::validation(
+            "curve_value",
+            "Curve value must be a number",
+        )),
+        _ => Ok(()),
+    }
+}
+
+async fn load_assignment_grades(
+    pool: &SqlitePool,
+    assignment_id: i64,
+) -> Result<(Assignment, Vec<Grade>), sqlx::Error> {
+    let assignment = load_assignment(pool, assignment_id).await?;
+
+    let grades = sqlx::query_as::<_, Grade>(
+        "SELECT g.STUDENT_ID, g.ASSIGNMENT_ID, g.SCORE, g.STATUS, g.SUBMITTED_AT
+         FROM GRADES g
+         JOIN STUDENT_CLASSES sc ON sc.STUDENT_ID = g.STUDENT_ID AND sc.CLASS_ID = ?
+         WHERE g.ASSIGNMENT_ID = ?
+         ORDER BY g.STUDENT_ID",
+    )
+    .bind(assignment.class_id)
+    .bind(assignment_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok((assignment, grades))
+}
+
+/// The before and after of a curve, without storing it.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn preview_curve(
+    state: State<'_, Mutex<AppState>>,
+    assignment_id: i64,
+    curve_kind: CurveKind,
+    curve_value: Option<f64>,
+) -> Result<Vec<CurvedScore>, GradifyError> {
+    validate_curve(curve_kind, curve_value)?;
+    let state = state.lock().await;
+
+    let (assignment, grades) = load_assignment_grades(&state.db.pool, assignment_id).await?;
+
+    Ok(grading::preview_curve(
+        curve_kind,
+        curve_value,
+        &assignment,
+        &grades,
+    ))
+}
+
+/// Stores the curve of an assignment, replacing any curve it already had.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn apply_curve(
+    state: State<'_, Mutex<AppState>>,
+    assignment_id: i64,
+    curve_kind: CurveKind,
+    curve_value: Option<f64>,
+) -> Result<AssignmentCurve, GradifyError> {
+    validate_curve(curve_kind, curve_value)?;
+    let state = state.lock().await;
+
+    sqlx::query(
+        "INSERT INTO ASSIGNMENT_CURVES (ASSIGNMENT_ID, CURVE_KIND, CURVE_VALUE)
+         VALUES (?, ?, ?)
+         ON CONFLICT (ASSIGNMENT_ID) DO UPDATE
+         SET CURVE_KIND = excluded.CURVE_KIND,
+             CURVE_VALUE = excluded.CURVE_VALUE,
+             APPLIED_AT = CURRENT_TIMESTAMP",
+    )
+    .bind(assignment_id)
+    .bind(curve_kind)
+    .bind(curve_value)
+    .execute(&state.db.pool)
+    .await?;
+
+    let curve = sqlx::query_as::<_, AssignmentCurve>(
+        "SELECT ASSIGNMENT_ID, CURVE_KIND, CURVE_VALUE, APPLIED_AT
+         FROM ASSIGNMENT_CURVES
+         WHERE ASSIGNMENT_ID = ?",
+    )
+    .bind(assignment_id)
+    .fetch_one(&state.db.pool)
+    .await?;
+
+    Ok(curve)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_assignment_curve(
+    state: State<'_, Mutex<AppState>>,
+    assignment_id: i64,
+) -> Result<Option<AssignmentCurve>, GradifyError> {
+    let state = state.lock().await;
+    let curve = sqlx::query_as::<_, AssignmentCurve>(
+        "SELECT ASSIGNMENT_ID, CURVE_KIND, CURVE_VALUE, APPLIED_AT
+         FROM ASSIGNMENT_CURVES
+         WHERE ASSIGNMENT_ID = ?",
+    )
+    .bind(assignment_id)
+    .fetch_optional(&state.db.pool)
+    .await?;
+    Ok(curve)
+}
+
+/// Undoes the curve of an assignment, bringing back its raw scores.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn remove_curve(
+    state: State<'_, Mutex<AppState>>,
+    assignment_id: i64,
+) -> Result<(), GradifyError> {
+    let state = state.lock().await;
+    sqlx::query("DELETE FROM ASSIGNMENT_CURVES WHERE ASSIGNMENT_ID = ?")
+        .bind(assignment_id)
+        .execute(&state.db.pool)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use crate::{database::models::DueDateOverride, error::GradifyError, AppState};
+use chrono::NaiveDateTime;
+use tauri::State;
+use tokio::sync::Mutex;
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn create_due_date_override(
+    state: State<'_, Mutex<AppState>>,
+    student_id: i64,
+    assignment_id: i64,
+    due_date: NaiveDateTime,
+    reason: Option<String>,
+) -> Result<DueDateOverride, GradifyError> {
+    let state = state.lock().await;
+
+    sqlx::query(
+        "INSERT INTO DUE_DATE_OVERRIDES (STUDENT_ID, ASSIGNMENT_ID, DUE_DATE, REASON)
+         VALUES (?, ?, ?, ?)",
+    )
+    .bind(student_id)
+    .bind(assignment_id)
+    .bind(due_date)
+    .bind(reason)
+    .execute(&state.db.pool)
+    .await?;
+
+    let due_date_override = sqlx::query_as::<_, DueDateOverride>(
+        "SELECT STUDENT_ID, ASSIGNMENT_ID, DUE_DATE, REASON
+         FROM DUE_DATE_OVERRIDES
+         WHERE STUDENT_ID = ? AND ASSIGNMENT_ID = ?",
+    )
+    .bind(student_id)
+    .bind(assignment_id)
+    .fetch_one(&state.db.pool)
+    .await?;
+
+    Ok(due_date_override)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_all_due_date_overrides(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Vec<DueDateOverride>, GradifyError> {
+    let state = state.lock().await;
+    let due_date_overrides = sqlx::query_as::<_, DueDateOverride>(
+        "SELECT STUDENT_ID, ASSIGNMENT_ID, DUE_DATE, REASON FROM DUE_DATE_OVERRIDES",
+    )
+    .fetch_all(&state.db.pool)
+    .await?;
+    Ok(due_date_overrides)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_assignment_due_date_overrides(
+    state: State<'_, Mutex<AppState>>,
+    assignment_id: i64,
+) -> Result<Vec<DueDateOverride>, GradifyError> {
+    let state = state.lock().await;
+    let due_date_overrides = sqlx::query_as::<_, DueDateOverride>(
+        "SELECT STUDENT_ID, ASSIGNMENT_ID, DUE_DATE, REASON
+         FROM DUE_DATE_OVERRIDES
+         WHERE ASSIGNMENT_ID = ?",
+    )
+    .bind(assignment_id)
+    .fetch_all(&state.db.pool)
+    .await?;
+    Ok(due_date_overrides)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn delete_due_date_override(
+    state: State<'_, Mutex<AppState>>,
+    student_id: i64,
+    assignment_id: i64,
+) -> Result<(), GradifyError> {
+    let state = state.lock().await;
+    sqlx::query("DELETE FROM DUE_DATE_OVERRIDES WHERE STUDENT_ID = ? AND ASSIGNMENT_ID = ?")
+        .bind(student_id)
+        .bind(assignment_id)
+        .execute(&state.db.pool)
+        .await?;
+    Ok(())
+}
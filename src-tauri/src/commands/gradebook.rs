@@ -0,0 +1,54 @@
+use crate::{
+    commands::overall_grades::{
+        calculate_class_grades, load_class_assignments, load_class_grades, load_class_students,
+        load_grading_policy,
+    },
+    database::models::GradebookExportOptions,
+    error::GradifyError,
+    gradebook, AppState,
+};
+use std::{io, path::PathBuf};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Writes the gradebook of a class to a CSV file at `file_path`, one row per
+/// enrolled student.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn export_class_gradebook_csv(
+    state: State<'_, Mutex<AppState>>,
+    class_id: i64,
+    file_path: PathBuf,
+    options: Option<GradebookExportOptions>,
+) -> Result<(), GradifyError> {
+    let state = state.lock().await;
+    let pool = &state.db.pool;
+
+    sqlx::query_scalar::<_, i64>("SELECT ID FROM CLASSES WHERE ID = ?")
+        .bind(class_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| GradifyError::not_found(format!("Class {class_id}")))?;
+
+    let students = load_class_students(pool, class_id).await?;
+    let policy = load_grading_policy(pool, class_id).await?;
+    let assignments = load_class_assignments(pool, class_id).await?;
+    let grades = load_class_grades(pool, class_id).await?;
+    let overall_grades = calculate_class_grades(pool, class_id).await?;
+
+    let rows = gradebook::gradebook_rows(
+        &students,
+        &policy.categories,
+        &assignments,
+        &grades,
+        &overall_grades,
+        options.unwrap_or_default(),
+    );
+
+    let mut writer = csv::Writer::from_path(&file_path).map_err(io::Error::from)?;
+    for row in rows {
+        writer.write_record(&row).map_err(io::Error::from)?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
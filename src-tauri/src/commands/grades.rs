@@ -1,4 +1,18 @@
-use crate::{database::models::Grade, AppState};
+use crate::{
+    commands::overall_grades::load_class_students,
+    database::models::{
+        BulkGradeReport, BulkGradeResult, Grade, GradeImportReport, GradeImportRow,
+        GradeImportStatus, GradeStatus,
+    },
+    error::GradifyError,
+    grade_import, AppState,
+};
+use chrono::NaiveDateTime;
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    fs::File,
+    path::PathBuf,
+};
 use tauri::State;
 use tokio::sync::Mutex;
 
@@ -7,30 +21,38 @@ pub async fn create_grade(
     state: State<'_, Mutex<AppState>>,
     student_id: i64,
     assignment_id: i64,
-    score: f64,
-) -> Result<Grade, String> {
+    score: Option<f64>,
+    status: GradeStatus,
+    submitted_at: Option<NaiveDateTime>,
+) -> Result<Grade, GradifyError> {
+    if status == GradeStatus::Graded && score.is_none() {
+        return Err(GradifyError::validation(
+            "score",
+            "A graded grade needs a score",
+        ));
+    }
     let state = state.lock().await;
 
     sqlx::query(
-        "INSERT INTO GRADES (STUDENT_ID, ASSIGNMENT_ID, SCORE)
-         VALUES (?, ?, ?)",
+        "INSERT INTO GRADES (STUDENT_ID, ASSIGNMENT_ID, SCORE, STATUS, SUBMITTED_AT)
+         VALUES (?, ?, ?, ?, ?)",
     )
     .bind(student_id)
     .bind(assignment_id)
     .bind(score)
+    .bind(status)
+    .bind(submitted_at)
     .execute(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
     let grade = sqlx::query_as::<_, Grade>(
-        "SELECT STUDENT_ID, ASSIGNMENT_ID, SCORE FROM GRADES
+        "SELECT STUDENT_ID, ASSIGNMENT_ID, SCORE, STATUS, SUBMITTED_AT FROM GRADES
          WHERE STUDENT_ID = ? AND ASSIGNMENT_ID = ?",
     )
     .bind(student_id)
     .bind(assignment_id)
     .fetch_one(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
     Ok(grade)
 }
@@ -40,27 +62,27 @@ pub async fn get_grade(
     state: State<'_, Mutex<AppState>>,
     student_id: i64,
     assignment_id: i64,
-) -> Result<Grade, String> {
+) -> Result<Grade, GradifyError> {
     let state = state.lock().await;
     let grade = sqlx::query_as::<_, Grade>(
-        "SELECT STUDENT_ID, ASSIGNMENT_ID, SCORE FROM GRADES
+        "SELECT STUDENT_ID, ASSIGNMENT_ID, SCORE, STATUS, SUBMITTED_AT FROM GRADES
          WHERE STUDENT_ID = ? AND ASSIGNMENT_ID = ?",
     )
     .bind(student_id)
     .bind(assignment_id)
     .fetch_one(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
     Ok(grade)
 }
 
 #[tauri::command(async, rename_all = "snake_case")]
-pub async fn get_all_grades(state: State<'_, Mutex<AppState>>) -> Result<Vec<Grade>, String> {
+pub async fn get_all_grades(state: State<'_, Mutex<AppState>>) -> Result<Vec<Grade>, GradifyError> {
     let state = state.lock().await;
-    let grades = sqlx::query_as::<_, Grade>("SELECT STUDENT_ID, ASSIGNMENT_ID, SCORE FROM GRADES")
-        .fetch_all(&state.db.pool)
-        .await
-        .map_err(|e| e.to_string())?;
+    let grades = sqlx::query_as::<_, Grade>(
+        "SELECT STUDENT_ID, ASSIGNMENT_ID, SCORE, STATUS, SUBMITTED_AT FROM GRADES",
+    )
+    .fetch_all(&state.db.pool)
+    .await?;
     Ok(grades)
 }
 
@@ -69,31 +91,92 @@ pub async fn update_grade(
     state: State<'_, Mutex<AppState>>,
     student_id: i64,
     assignment_id: i64,
-    score: f64,
-) -> Result<Grade, String> {
+    score: Option<f64>,
+    status: GradeStatus,
+    submitted_at: Option<NaiveDateTime>,
+) -> Result<Grade, GradifyError> {
+    if status == GradeStatus::Graded && score.is_none() {
+        return Err(GradifyError::validation(
+            "score",
+            "A graded grade needs a score",
+        ));
+    }
     let state = state.lock().await;
 
-    sqlx::query(
-        "UPDATE GRADES 
-         SET SCORE = ? 
+    let result = sqlx::query(
+        "UPDATE GRADES
+         SET SCORE = ?, STATUS = ?, SUBMITTED_AT = ?
          WHERE STUDENT_ID = ? AND ASSIGNMENT_ID = ?",
     )
     .bind(score)
+    .bind(status)
+    .bind(submitted_at)
+    .bind(student_id)
+    .bind(assignment_id)
+    .execute(&state.db.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(GradifyError::not_found(format!(
+            "Grade for student {student_id} on assignment {assignment_id}"
+        )));
+    }
+
+    let grade = sqlx::query_as::<_, Grade>(
+        "SELECT STUDENT_ID, ASSIGNMENT_ID, SCORE, STATUS, SUBMITTED_AT FROM GRADES
+         WHERE STUDENT_ID = ? AND ASSIGNMENT_ID = ?",
+    )
+    .bind(student_id)
+    .bind(assignment_id)
+    .fetch_one(&state.db.pool)
+    .await?;
+
+    Ok(grade)
+}
+
+/// Creates the grade, or replaces it if the student already has one for the
+/// assignment.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn set_grade(
+    state: State<'_, Mutex<AppState>>,
+    student_id: i64,
+    assignment_id: i64,
+    score: Option<f64>,
+    status: GradeStatus,
+    submitted_at: Option<NaiveDateTime>,
+) -> Result<Grade, GradifyError> {
+    if status == GradeStatus::Graded && score.is_none() {
+        return Err(GradifyError::validation(
+            "score",
+            "A graded grade needs a score",
+        ));
+    }
+    let state = state.lock().await;
+
+    sqlx::query(
+        "INSERT INTO GRADES (STUDENT_ID, ASSIGNMENT_ID, SCORE, STATUS, SUBMITTED_AT)
+         VALUES (?, ?, ?, ?, ?)
+         ON CONFLICT (STUDENT_ID, ASSIGNMENT_ID) DO UPDATE
+         SET SCORE = excluded.SCORE,
+             STATUS = excluded.STATUS,
+             SUBMITTED_AT = excluded.SUBMITTED_AT",
+    )
     .bind(student_id)
     .bind(assignment_id)
+    .bind(score)
+    .bind(status)
+    .bind(submitted_at)
     .execute(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
     let grade = sqlx::query_as::<_, Grade>(
-        "SELECT STUDENT_ID, ASSIGNMENT_ID, SCORE FROM GRADES
+        "SELECT STUDENT_ID, ASSIGNMENT_ID, SCORE, STATUS, SUBMITTED_AT FROM GRADES
          WHERE STUDENT_ID = ? AND ASSIGNMENT_ID = ?",
     )
     .bind(student_id)
     .bind(assignment_id)
     .fetch_one(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
     Ok(grade)
 }
@@ -103,7 +186,7 @@ pub async fn delete_grade(
     state: State<'_, Mutex<AppState>>,
     student_id: i64,
     assignment_id: i64,
-) -> Result<(), String> {
+) -> Result<(), GradifyError> {
     let state = state.lock().await;
     sqlx::query(
         "DELETE FROM GRADES
@@ -112,7 +195,308 @@ pub async fn delete_grade(
     .bind(student_id)
     .bind(assignment_id)
     .execute(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
     Ok(())
 }
+
+/// Checks a grade being saved in bulk or imported against its assignment,
+/// given as its maximum score and whether it allows extra credit.
+fn validate_bulk_grade(grade: &Grade, assignment: Option<(f64, bool)>) -> Option<String> {
+    let Some((maximum_score, allows_extra_credit)) = assignment else {
+        return Some("Assignment not found".to_string());
+    };
+    match grade.score {
+        None if grade.status == GradeStatus::Graded => {
+            Some("A graded grade needs a score".to_string())
+        }
+        Some(score) if !score.is_finite() => Some("Score must be a number".to_string()),
+        Some(score) if score < 0.0 => Some("Score cannot be negative".to_string()),
+        Some(score) if score > maximum_score && !allows_extra_credit => Some(format!(
+            "Score cannot exceed the assignment maximum score of {maximum_score}"
+        )),
+        _ => None,
+    }
+}
+
+/// Checks every grade being saved in bulk against its assignment in
+/// `assignments`, which holds `None` for assignments that do not exist. Only
+/// the first of several grades for the same student and assignment can pass.
+fn validate_bulk_grades(
+    grades: &[Grade],
+    assignments: &HashMap<i64, Option<(f64, bool)>>,
+) -> Vec<BulkGradeResult> {
+    let mut seen = HashSet::new();
+    grades
+        .iter()
+        .map(|grade| {
+            let error = if seen.insert((grade.student_id, grade.assignment_id)) {
+                validate_bulk_grade(
+                    grade,
+                    assignments.get(&grade.assignment_id).copied().flatten(),
+                )
+            } else {
+                Some("Grade appears more than once".to_string())
+            };
+            BulkGradeResult {
+                student_id: grade.student_id,
+                assignment_id: grade.assignment_id,
+                error,
+            }
+        })
+        .collect()
+}
+
+/// Saves many grades in one transaction, inserting new grades and replacing
+/// existing ones. Every row is validated before anything is written, so a
+/// score that is negative or past the maximum is reported against its row.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn save_grades_bulk(
+    state: State<'_, Mutex<AppState>>,
+    grades: Vec<Grade>,
+) -> Result<BulkGradeReport, GradifyError> {
+    let state = state.lock().await;
+    let mut tx = state.db.pool.begin().await?;
+
+    let mut assignments: HashMap<i64, Option<(f64, bool)>> = HashMap::new();
+    for grade in &grades {
+        if let Entry::Vacant(entry) = assignments.entry(grade.assignment_id) {
+            let assignment = sqlx::query_as::<_, (f64, bool)>(
+                "SELECT MAXIMUM_SCORE, ALLOWS_EXTRA_CREDIT FROM ASSIGNMENTS WHERE ID = ?",
+            )
+            .bind(grade.assignment_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+            entry.insert(assignment);
+        }
+    }
+
+    let mut results = validate_bulk_grades(&grades, &assignments);
+
+    if results.iter().any(|r| r.error.is_some()) {
+        tx.rollback().await?;
+        return Ok(BulkGradeReport {
+            committed: false,
+            results,
+        });
+    }
+
+    for (grade, result) in grades.iter().zip(results.iter_mut()) {
+        let saved = sqlx::query(
+            "INSERT INTO GRADES (STUDENT_ID, ASSIGNMENT_ID, SCORE, STATUS, SUBMITTED_AT)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT (STUDENT_ID, ASSIGNMENT_ID) DO UPDATE
+             SET SCORE = excluded.SCORE,
+                 STATUS = excluded.STATUS,
+                 SUBMITTED_AT = excluded.SUBMITTED_AT",
+        )
+        .bind(grade.student_id)
+        .bind(grade.assignment_id)
+        .bind(grade.score)
+        .bind(grade.status)
+        .bind(grade.submitted_at)
+        .execute(&mut *tx)
+        .await;
+        if let Err(e) = saved {
+            result.error = Some(GradifyError::from(e).to_string());
+        }
+    }
+
+    let committed = results.iter().all(|r| r.error.is_none());
+    if committed {
+        tx.commit().await?;
+    } else {
+        tx.rollback().await?;
+    }
+
+    Ok(BulkGradeReport { committed, results })
+}
+
+/// Imports the scores of one assignment from a CSV file, matching rows to the
+/// class's students by student ID or email address. Valid rows are saved in
+/// one transaction and the rest are reported. With `only_empty` set, grades
+/// that already have a score or are excused are kept; with `preview` set,
+/// nothing is saved.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn import_assignment_grades_csv(
+    state: State<'_, Mutex<AppState>>,
+    assignment_id: i64,
+    file_path: PathBuf,
+    student_column: String,
+    score_column: String,
+    only_empty: bool,
+    preview: bool,
+) -> Result<GradeImportReport, GradifyError> {
+    let mut rows =
+        grade_import::parse_grade_rows(File::open(&file_path)?, &student_column, &score_column)?;
+    let state = state.lock().await;
+
+    let (class_id, maximum_score, allows_extra_credit) = sqlx::query_as::<_, (i64, f64, bool)>(
+        "SELECT CLASS_ID, MAXIMUM_SCORE, ALLOWS_EXTRA_CREDIT FROM ASSIGNMENTS WHERE ID = ?",
+    )
+    .bind(assignment_id)
+    .fetch_optional(&state.db.pool)
+    .await?
+    .ok_or_else(|| GradifyError::not_found(format!("Assignment {assignment_id}")))?;
+
+    let students = load_class_students(&state.db.pool, class_id).await?;
+    grade_import::match_students(&mut rows, &students);
+
+    let existing_grades: HashMap<i64, (Option<f64>, GradeStatus)> =
+        sqlx::query_as::<_, (i64, Option<f64>, GradeStatus)>(
+            "SELECT STUDENT_ID, SCORE, STATUS FROM GRADES WHERE ASSIGNMENT_ID = ?",
+        )
+        .bind(assignment_id)
+        .fetch_all(&state.db.pool)
+        .await?
+        .into_iter()
+        .map(|(student_id, score, status)| (student_id, (score, status)))
+        .collect();
+
+    classify_import_rows(
+        &mut rows,
+        assignment_id,
+        (maximum_score, allows_extra_credit),
+        &existing_grades,
+        only_empty,
+    );
+
+    if preview {
+        return Ok(GradeImportReport {
+            committed: false,
+            imported: 0,
+            rows,
+        });
+    }
+
+    let mut tx = state.db.pool.begin().await?;
+    let mut imported = 0;
+    for row in &mut rows {
+        if !matches!(
+            row.status,
+            GradeImportStatus::New | GradeImportStatus::Replaces
+        ) {
+            continue;
+        }
+        // Keeps SUBMITTED_AT, which the file says nothing about.
+        let saved = sqlx::query(
+            "INSERT INTO GRADES (STUDENT_ID, ASSIGNMENT_ID, SCORE, STATUS)
+             VALUES (?, ?, ?, ?)
+             ON CONFLICT (STUDENT_ID, ASSIGNMENT_ID) DO UPDATE
+             SET SCORE = excluded.SCORE,
+                 STATUS = excluded.STATUS",
+        )
+        .bind(row.student_id)
+        .bind(assignment_id)
+        .bind(row.score)
+        .bind(GradeStatus::Graded)
+        .execute(&mut *tx)
+        .await;
+        match saved {
+            Ok(_) => imported += 1,
+            Err(e) => {
+                row.status = GradeImportStatus::Invalid;
+                row.error = Some(GradifyError::from(e).to_string());
+            }
+        }
+    }
+    tx.commit().await?;
+
+    Ok(GradeImportReport {
+        committed: true,
+        imported,
+        rows,
+    })
+}
+
+/// Gives each matched row of an import into `assignment_id` its status: rows
+/// whose score does not fit the assignment become `Invalid`, and the rest
+/// `New`, `Replaces` or, with `only_empty` set, `Kept`, depending on the
+/// student's grade in `existing_grades`.
+fn classify_import_rows(
+    rows: &mut [GradeImportRow],
+    assignment_id: i64,
+    assignment: (f64, bool),
+    existing_grades: &HashMap<i64, (Option<f64>, GradeStatus)>,
+    only_empty: bool,
+) {
+    for row in rows
+        .iter_mut()
+        .filter(|r| r.status == GradeImportStatus::New)
+    {
+        let Some(student_id) = row.student_id else {
+            continue;
+        };
+        let grade = Grade {
+            student_id,
+            assignment_id,
+            score: row.score,
+            status: GradeStatus::Graded,
+            submitted_at: None,
+        };
+        if let Some(error) = validate_bulk_grade(&grade, Some(assignment)) {
+            row.status = GradeImportStatus::Invalid;
+            row.error = Some(error);
+            continue;
+        }
+        row.status = match existing_grades.get(&student_id) {
+            Some(&(score, status))
+                if only_empty && !grade_import::is_empty_grade(score, status) =>
+            {
+                GradeImportStatus::Kept
+            }
+            Some(_) => GradeImportStatus::Replaces,
+            None => GradeImportStatus::New,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import_row(student_id: i64, score: f64) -> GradeImportRow {
+        GradeImportRow {
+            line: 2,
+            student: student_id.to_string(),
+            student_id: Some(student_id),
+            score: Some(score),
+            status: GradeImportStatus::New,
+            error: None,
+        }
+    }
+
+    fn grade(student_id: i64, score: f64) -> Grade {
+        Grade {
+            student_id,
+            assignment_id: 1,
+            score: Some(score),
+            status: GradeStatus::Graded,
+            submitted_at: None,
+        }
+    }
+
+    #[test]
+    fn bulk_saves_report_negative_scores_against_their_row() {
+        let assignments = HashMap::from([(1, Some((10.0, false)))]);
+
+        let results = validate_bulk_grades(&[grade(1, 8.0), grade(2, -1.0)], &assignments);
+
+        assert_eq!(results[0].error, None);
+        assert_eq!(
+            results[1].error.as_deref(),
+            Some("Score cannot be negative")
+        );
+    }
+
+    #[test]
+    fn import_preview_flags_negative_scores() {
+        let mut rows = [import_row(1, -5.0), import_row(2, 7.0)];
+        let existing_grades = HashMap::from([(1, (Some(4.0), GradeStatus::Graded))]);
+
+        classify_import_rows(&mut rows, 1, (10.0, false), &existing_grades, false);
+
+        assert_eq!(rows[0].status, GradeImportStatus::Invalid);
+        assert_eq!(rows[0].error.as_deref(), Some("Score cannot be negative"));
+        assert_eq!(rows[1].status, GradeImportStatus::New);
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{
+    database::models::{GradeThreshold, GradingScale},
+    error::GradifyError,
+    validation, AppState,
+};
+use sqlx::{Sqlite, SqliteConnection, Transaction};
+use tauri::State;
+use tokio::sync::Mutex;
+
+fn validate_thresholds(thresholds: &[GradeThreshold]) -> Result<(), GradifyError> {
+    if thresholds.is_empty() {
+        return Err(GradifyError::validation(
+            "thresholds",
+            "A grading scale needs at least one threshold",
+        ));
+    }
+    for threshold in thresholds {
+        if threshold.letter_grade.trim().is_empty() {
+            return Err(GradifyError::validation(
+                "letter_grade",
+                "Letter grades cannot be empty",
+            ));
+        }
+        if !threshold.min_percentage.is_finite() || threshold.min_percentage < 0.0 {
+            return Err(GradifyError::validation(
+                "min_percentage",
+                format!(
+                    "Invalid minimum percentage for '{}'",
+                    threshold.letter_grade
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+async fn insert_thresholds(
+    tx: &mut Transaction<'_, Sqlite>,
+    scale_id: i64,
+    thresholds: &[GradeThreshold],
+) -> Result<(), sqlx::Error> {
+    for threshold in thresholds {
+        sqlx::query(
+            "INSERT INTO GRADING_SCALE_THRESHOLDS (SCALE_ID, LETTER_GRADE, MIN_PERCENTAGE)
+             VALUES (?, ?, ?)",
+        )
+        .bind(scale_id)
+        .bind(threshold.letter_grade.trim())
+        .bind(threshold.min_percentage)
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(())
+}
+
+async fn fetch_grading_scale(
+    conn: &mut SqliteConnection,
+    id: i64,
+) -> Result<GradingScale, sqlx::Error> {
+    let mut scale = sqlx::query_as::<_, GradingScale>(
+        "SELECT ID, SCALE_NAME, IS_DEFAULT FROM GRADING_SCALES WHERE ID = ?",
+    )
+    .bind(id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    scale.thresholds = sqlx::query_as::<_, GradeThreshold>(
+        "SELECT LETTER_GRADE, MIN_PERCENTAGE FROM GRADING_SCALE_THRESHOLDS
+         WHERE SCALE_ID = ?
+         ORDER BY MIN_PERCENTAGE DESC",
+    )
+    .bind(id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(scale)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn create_grading_scale(
+    state: State<'_, Mutex<AppState>>,
+    scale_name: String,
+    thresholds: Vec<GradeThreshold>,
+) -> Result<GradingScale, GradifyError> {
+    let scale_name = validation::required_text("scale_name", &scale_name)?;
+    validate_thresholds(&thresholds)?;
+    let state = state.lock().await;
+    let mut tx = state.db.pool.begin().await?;
+
+    let result = sqlx::query("INSERT INTO GRADING_SCALES (SCALE_NAME) VALUES (?)")
+        .bind(&scale_name)
+        .execute(&mut *tx)
+        .await?;
+
+    let id = result.last_insert_rowid();
+
+    insert_thresholds(&mut tx, id, &thresholds).await?;
+
+    let scale = fetch_grading_scale(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(scale)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_grading_scale(
+    state: State<'_, Mutex<AppState>>,
+    id: i64,
+) -> Result<GradingScale, GradifyError> {
+    let state = state.lock().await;
+    let mut conn = state.db.pool.acquire().await?;
+    let scale = fetch_grading_scale(&mut conn, id).await?;
+    Ok(scale)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_all_grading_scales(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Vec<GradingScale>, GradifyError> {
+    let state = state.lock().await;
+    let mut conn = state.db.pool.acquire().await?;
+
+    let ids = sqlx::query_scalar::<_, i64>("SELECT ID FROM GRADING_SCALES ORDER BY ID")
+        .fetch_all(&mut *conn)
+        .await?;
+
+    let mut scales = Vec::with_capacity(ids.len());
+    for id in ids {
+        let scale = fetch_grading_scale(&mut conn, id).await?;
+        scales.push(scale);
+    }
+
+    Ok(scales)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn update_grading_scale(
+    state: State<'_, Mutex<AppState>>,
+    id: i64,
+    scale_name: String,
+    thresholds: Vec<GradeThreshold>,
+) -> Result<GradingScale, GradifyError> {
+    let scale_name = validation::required_text("scale_name", &scale_name)?;
+    validate_thresholds(&thresholds)?;
+    let state = state.lock().await;
+    let mut tx = state.db.pool.begin().await?;
+
+    let result = sqlx::query(
+        "UPDATE GRADING_SCALES
+         SET SCALE_NAME = ?
+         WHERE ID = ?",
+    )
+    .bind(&scale_name)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        tx.rollback().await?;
+        return Err(GradifyError::not_found(format!("Grading scale {id}")));
+    }
+
+    sqlx::query("DELETE FROM GRADING_SCALE_THRESHOLDS WHERE SCALE_ID = ?")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+    insert_thresholds(&mut tx, id, &thresholds).await?;
+
+    let scale = fetch_grading_scale(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(scale)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn delete_grading_scale(
+    state: State<'_, Mutex<AppState>>,
+    id: i64,
+) -> Result<(), GradifyError> {
+    let state = state.lock().await;
+    sqlx::query("DELETE FROM GRADING_SCALES WHERE ID = ?")
+        .bind(id)
+        .execute(&state.db.pool)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use crate::{
+    commands::overall_grades::{load_class_assignments, load_class_grades, load_grading_policy},
+    database::models::{LatePolicy, LateSubmission},
+    error::GradifyError,
+    grading, validation, AppState,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Longest grace period a late policy can allow, a year.
+const MAX_GRACE_PERIOD_HOURS: f64 = 24.0 * 365.0;
+/// Latest day after which late work can be zeroed.
+const MAX_ZERO_AFTER_DAYS: i64 = 365;
+
+/// Sets the late policy of a class, or of one of its assignments when
+/// `assignment_id` is given. Penalties are percentages of the score.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn set_late_policy(
+    state: State<'_, Mutex<AppState>>,
+    class_id: i64,
+    assignment_id: Option<i64>,
+    penalty_per_day: f64,
+    max_penalty: Option<f64>,
+    grace_period_hours: f64,
+    zero_after_days: Option<i64>,
+) -> Result<LatePolicy, GradifyError> {
+    let penalty_per_day = validation::number_in("penalty_per_day", penalty_per_day, 0.0..=100.0)?;
+    let max_penalty = max_penalty
+        .map(|max_penalty| validation::number_in("max_penalty", max_penalty, 0.0..=100.0))
+        .transpose()?;
+    let grace_period_hours = validation::number_in(
+        "grace_period_hours",
+        grace_period_hours,
+        0.0..=MAX_GRACE_PERIOD_HOURS,
+    )?;
+    let zero_after_days = zero_after_days
+        .map(|days| validation::whole_number_in("zero_after_days", days, 0..=MAX_ZERO_AFTER_DAYS))
+        .transpose()?;
+
+    let state = state.lock().await;
+
+    if let Some(assignment_id) = assignment_id {
+        let assignment_class_id =
+            sqlx::query_scalar::<_, i64>("SELECT CLASS_ID FROM ASSIGNMENTS WHERE ID = ?")
+                .bind(assignment_id)
+                .fetch_optional(&state.db.pool)
+                .await?
+                .ok_or_else(|| GradifyError::not_found(format!("Assignment {assignment_id}")))?;
+        if assignment_class_id != class_id {
+            return Err(GradifyError::validation(
+                "assignment_id",
+                format!("Assignment {assignment_id} does not belong to class {class_id}"),
+            ));
+        }
+    }
+
+    let existing_id = sqlx::query_scalar::<_, i64>(
+        "SELECT ID FROM LATE_POLICIES WHERE CLASS_ID = ? AND ASSIGNMENT_ID IS ?",
+    )
+    .bind(class_id)
+    .bind(assignment_id)
+    .fetch_optional(&state.db.pool)
+    .await?;
+
+    let id = match existing_id {
+        Some(id) => {
+            sqlx::query(
+                "UPDATE LATE_POLICIES
+                 SET PENALTY_PER_DAY = ?, MAX_PENALTY = ?, GRACE_PERIOD_HOURS = ?, ZERO_AFTER_DAYS = ?
+                 WHERE ID = ?",
+            )
+            .bind(penalty_per_day)
+            .bind(max_penalty)
+            .bind(grace_period_hours)
+            .bind(zero_after_days)
+            .bind(id)
+            .execute(&state.db.pool)
+            .await?;
+            id
+        }
+        None => sqlx::query(
+            "INSERT INTO LATE_POLICIES
+                 (CLASS_ID, ASSIGNMENT_ID, PENALTY_PER_DAY, MAX_PENALTY, GRACE_PERIOD_HOURS, ZERO_AFTER_DAYS)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(class_id)
+        .bind(assignment_id)
+        .bind(penalty_per_day)
+        .bind(max_penalty)
+        .bind(grace_period_hours)
+        .bind(zero_after_days)
+        .execute(&state.db.pool)
+        .await?
+        .last_insert_rowid(),
+    };
+
+    let late_policy = sqlx::query_as::<_, LatePolicy>(
+        "SELECT ID, CLASS_ID, ASSIGNMENT_ID, PENALTY_PER_DAY, MAX_PENALTY, GRACE_PERIOD_HOURS, ZERO_AFTER_DAYS
+         FROM LATE_POLICIES
+         WHERE ID = ?",
+    )
+    .bind(id)
+    .fetch_one(&state.db.pool)
+    .await?;
+
+    Ok(late_policy)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_late_policies(
+    state: State<'_, Mutex<AppState>>,
+    class_id: i64,
+) -> Result<Vec<LatePolicy>, GradifyError> {
+    let state = state.lock().await;
+    let late_policies = sqlx::query_as::<_, LatePolicy>(
+        "SELECT ID, CLASS_ID, ASSIGNMENT_ID, PENALTY_PER_DAY, MAX_PENALTY, GRACE_PERIOD_HOURS, ZERO_AFTER_DAYS
+         FROM LATE_POLICIES
+         WHERE CLASS_ID = ?",
+    )
+    .bind(class_id)
+    .fetch_all(&state.db.pool)
+    .await?;
+    Ok(late_policies)
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn delete_late_policy(
+    state: State<'_, Mutex<AppState>>,
+    id: i64,
+) -> Result<(), GradifyError> {
+    let state = state.lock().await;
+    sqlx::query("DELETE FROM LATE_POLICIES WHERE ID = ?")
+        .bind(id)
+        .execute(&state.db.pool)
+        .await?;
+    Ok(())
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_late_submissions(
+    state: State<'_, Mutex<AppState>>,
+    class_id: i64,
+) -> Result<Vec<LateSubmission>, GradifyError> {
+    let state = state.lock().await;
+
+    let policy = load_grading_policy(&state.db.pool, class_id).await?;
+    let assignments = load_class_assignments(&state.db.pool, class_id).await?;
+    let grades = load_class_grades(&state.db.pool, class_id).await?;
+
+    Ok(grading::late_submissions(&policy, &assignments, &grades))
+}
@@ -1,17 +1,210 @@
-use crate::{database::models::OverallGrade, AppState};
+use crate::{
+    database::models::{
+        Assignment, AssignmentCurve, Category, DueDateOverride, Grade, GradeThreshold, GradingMode,
+        LatePolicy, OverallGrade, Student, StudentAccommodation,
+    },
+    error::GradifyError,
+    grading::{self, GradingPolicy},
+    AppState,
+};
+use sqlx::SqlitePool;
 use tauri::State;
 use tokio::sync::Mutex;
 
+pub(crate) async fn load_grading_policy(
+    pool: &SqlitePool,
+    class_id: i64,
+) -> Result<GradingPolicy, sqlx::Error> {
+    let (grading_mode, scale_id) = sqlx::query_as::<_, (GradingMode, Option<i64>)>(
+        "SELECT GRADING_MODE, COALESCE(GRADING_SCALE_ID, (SELECT ID FROM GRADING_SCALES WHERE IS_DEFAULT = 1))
+         FROM CLASSES
+         WHERE ID = ?",
+    )
+    .bind(class_id)
+    .fetch_one(pool)
+    .await?;
+
+    let categories = sqlx::query_as::<_, Category>(
+        "SELECT ID, CLASS_ID, CATEGORY_NAME, WEIGHT, DROP_LOWEST, DROP_HIGHEST FROM CATEGORIES
+         WHERE CLASS_ID = ?
+         ORDER BY ID",
+    )
+    .bind(class_id)
+    .fetch_all(pool)
+    .await?;
+
+    let thresholds = sqlx::query_as::<_, GradeThreshold>(
+        "SELECT LETTER_GRADE, MIN_PERCENTAGE FROM GRADING_SCALE_THRESHOLDS WHERE SCALE_ID = ?",
+    )
+    .bind(scale_id)
+    .fetch_all(pool)
+    .await?;
+
+    let late_policies = sqlx::query_as::<_, LatePolicy>(
+        "SELECT ID, CLASS_ID, ASSIGNMENT_ID, PENALTY_PER_DAY, MAX_PENALTY, GRACE_PERIOD_HOURS, ZERO_AFTER_DAYS
+         FROM LATE_POLICIES
+         WHERE CLASS_ID = ?",
+    )
+    .bind(class_id)
+    .fetch_all(pool)
+    .await?;
+
+    let accommodations = sqlx::query_as::<_, StudentAccommodation>(
+        "SELECT ID, STUDENT_ID, CLASS_ID, EXTENSION_DAYS, TIME_MULTIPLIER, NOTES
+         FROM STUDENT_ACCOMMODATIONS
+         WHERE CLASS_ID = ? OR CLASS_ID IS NULL",
+    )
+    .bind(class_id)
+    .fetch_all(pool)
+    .await?;
+
+    let due_date_overrides = sqlx::query_as::<_, DueDateOverride>(
+        "SELECT o.STUDENT_ID, o.ASSIGNMENT_ID, o.DUE_DATE, o.REASON
+         FROM DUE_DATE_OVERRIDES o
+         JOIN ASSIGNMENTS a ON a.ID = o.ASSIGNMENT_ID
+         WHERE a.CLASS_ID = ?",
+    )
+    .bind(class_id)
+    .fetch_all(pool)
+    .await?;
+
+    let curves = sqlx::query_as::<_, AssignmentCurve>(
+        "SELECT cu.ASSIGNMENT_ID, cu.CURVE_KIND, cu.CURVE_VALUE, cu.APPLIED_AT
+         FROM ASSIGNMENT_CURVES cu
+         JOIN ASSIGNMENTS a ON a.ID = cu.ASSIGNMENT_ID
+         WHERE a.CLASS_ID = ?",
+    )
+    .bind(class_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(GradingPolicy {
+        class_id,
+        grading_mode,
+        categories,
+        thresholds,
+        late_policies,
+        accommodations,
+        due_date_overrides,
+        curves,
+    })
+}
+
+pub(crate) async fn load_assignment(
+    pool: &SqlitePool,
+    assignment_id: i64,
+) -> Result<Assignment, sqlx::Error> {
+    sqlx::query_as::<_, Assignment>(
+        "SELECT a.ID, a.CLASS_ID, a.ASSIGNMENT_NAME, a.CATEGORY_ID, c.CATEGORY_NAME, a.MAXIMUM_SCORE,
+                a.DUE_DATE, a.ALLOWS_EXTRA_CREDIT, a.ASSIGNMENT_KIND
+         FROM ASSIGNMENTS a
+         JOIN CATEGORIES c ON c.ID = a.CATEGORY_ID
+         WHERE a.ID = ?",
+    )
+    .bind(assignment_id)
+    .fetch_one(pool)
+    .await
+}
+
+pub(crate) async fn load_class_assignments(
+    pool: &SqlitePool,
+    class_id: i64,
+) -> Result<Vec<Assignment>, sqlx::Error> {
+    sqlx::query_as::<_, Assignment>(
+        "SELECT a.ID, a.CLASS_ID, a.ASSIGNMENT_NAME, a.CATEGORY_ID, c.CATEGORY_NAME, a.MAXIMUM_SCORE,
+                a.DUE_DATE, a.ALLOWS_EXTRA_CREDIT, a.ASSIGNMENT_KIND
+         FROM ASSIGNMENTS a
+         JOIN CATEGORIES c ON c.ID = a.CATEGORY_ID
+         WHERE a.CLASS_ID = ?",
+    )
+    .bind(class_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// The grades of the students enrolled in a class for its assignments. Grades
+/// of students who left the class stay in the database but are left out, so
+/// they do not skew curves.
+pub(crate) async fn load_class_grades(
+    pool: &SqlitePool,
+    class_id: i64,
+) -> Result<Vec<Grade>, sqlx::Error> {
+    sqlx::query_as::<_, Grade>(
+        "SELECT g.STUDENT_ID, g.ASSIGNMENT_ID, g.SCORE, g.STATUS, g.SUBMITTED_AT
+         FROM GRADES g
+         JOIN ASSIGNMENTS a ON a.ID = g.ASSIGNMENT_ID
+         JOIN STUDENT_CLASSES sc ON sc.STUDENT_ID = g.STUDENT_ID AND sc.CLASS_ID = a.CLASS_ID
+         WHERE a.CLASS_ID = ?",
+    )
+    .bind(class_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// The students enrolled in a class, by last name and then first name.
+pub(crate) async fn load_class_students(
+    pool: &SqlitePool,
+    class_id: i64,
+) -> Result<Vec<Student>, sqlx::Error> {
+    sqlx::query_as::<_, Student>(
+        "SELECT s.ID, s.FIRST_NAME, s.LAST_NAME, s.EMAIL
+         FROM STUDENTS s
+         JOIN STUDENT_CLASSES sc ON sc.STUDENT_ID = s.ID
+         WHERE sc.CLASS_ID = ?
+         ORDER BY s.LAST_NAME, s.FIRST_NAME, s.ID",
+    )
+    .bind(class_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// The IDs of the students enrolled in a class, in order.
+pub(crate) async fn load_class_student_ids(
+    pool: &SqlitePool,
+    class_id: i64,
+) -> Result<Vec<i64>, sqlx::Error> {
+    sqlx::query_scalar::<_, i64>(
+        "SELECT STUDENT_ID FROM STUDENT_CLASSES WHERE CLASS_ID = ? ORDER BY STUDENT_ID",
+    )
+    .bind(class_id)
+    .fetch_all(pool)
+    .await
+}
+
+pub(crate) async fn calculate_class_grades(
+    pool: &SqlitePool,
+    class_id: i64,
+) -> Result<Vec<OverallGrade>, sqlx::Error> {
+    let policy = load_grading_policy(pool, class_id).await?;
+
+    let student_ids = load_class_student_ids(pool, class_id).await?;
+
+    let assignments = load_class_assignments(pool, class_id).await?;
+    let grades = load_class_grades(pool, class_id).await?;
+
+    Ok(grading::calculate_overall_grades(
+        &policy,
+        &student_ids,
+        &assignments,
+        &grades,
+    ))
+}
+
 #[tauri::command(async, rename_all = "snake_case")]
 pub async fn get_overall_grades(
     state: State<'_, Mutex<AppState>>,
-) -> Result<Vec<OverallGrade>, String> {
+) -> Result<Vec<OverallGrade>, GradifyError> {
     let state = state.lock().await;
-    let overall_grades = sqlx::query_as::<_, OverallGrade>(
-        "SELECT STUDENT_ID, CLASS_ID, PERCENTAGE, LETTER_GRADE FROM OVERALL_GRADES",
-    )
-    .fetch_all(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+
+    let class_ids = sqlx::query_scalar::<_, i64>("SELECT ID FROM CLASSES ORDER BY ID")
+        .fetch_all(&state.db.pool)
+        .await?;
+
+    let mut overall_grades = Vec::new();
+    for class_id in class_ids {
+        let class_grades = calculate_class_grades(&state.db.pool, class_id).await?;
+        overall_grades.extend(class_grades);
+    }
+
     Ok(overall_grades)
 }
@@ -0,0 +1,83 @@
+use crate::{
+    database::{db::Database, location},
+    error::GradifyError,
+    AppState, StartupState,
+};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, State};
+use tokio::{fs, sync::Mutex};
+
+/// Why the database could not be opened at startup, or `None` when it is open.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_startup_error(
+    startup: State<'_, Mutex<StartupState>>,
+) -> Result<Option<String>, GradifyError> {
+    let startup = startup.lock().await;
+    Ok(startup.error.clone())
+}
+
+/// Replaces the database that failed to open with a copy of `backup_path`.
+/// The failed database is renamed, not deleted.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn restore_database(
+    app: AppHandle,
+    startup: State<'_, Mutex<StartupState>>,
+    backup_path: PathBuf,
+) -> Result<(), GradifyError> {
+    let mut startup = startup.lock().await;
+    ensure_recovering(&startup)?;
+    if !backup_path.is_file() {
+        return Err(GradifyError::not_found(format!(
+            "Backup {}",
+            backup_path.display()
+        )));
+    }
+
+    location::set_aside(&startup.db_path)?;
+    fs::copy(&backup_path, &startup.db_path).await?;
+    reopen(&app, &mut startup).await
+}
+
+/// Replaces the database that failed to open with an empty one. The failed
+/// database is renamed, not deleted.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn create_fresh_database(
+    app: AppHandle,
+    startup: State<'_, Mutex<StartupState>>,
+) -> Result<(), GradifyError> {
+    let mut startup = startup.lock().await;
+    ensure_recovering(&startup)?;
+
+    location::set_aside(&startup.db_path)?;
+    reopen(&app, &mut startup).await
+}
+
+fn ensure_recovering(startup: &StartupState) -> Result<(), GradifyError> {
+    match startup.error {
+        Some(_) => Ok(()),
+        None => Err(GradifyError::validation(
+            "database",
+            "The database is already open",
+        )),
+    }
+}
+
+async fn reopen(app: &AppHandle, startup: &mut StartupState) -> Result<(), GradifyError> {
+    match Database::open(&startup.db_path).await {
+        Ok(db) => {
+            // Set when a restore failed after the app had started normally.
+            match app.try_state::<Mutex<AppState>>() {
+                Some(state) => state.lock().await.db = db,
+                None => {
+                    app.manage(Mutex::new(AppState::new(db)));
+                }
+            }
+            startup.error = None;
+            Ok(())
+        }
+        Err(e) => {
+            startup.error = Some(e.to_string());
+            Err(e)
+        }
+    }
+}
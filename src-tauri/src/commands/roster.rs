@@ -0,0 +1,116 @@
+use crate::{
+    commands::student_classes::insert_enrollment,
+    database::models::{RosterColumns, RosterImportReport, RosterRowStatus},
+    error::GradifyError,
+    roster, AppState,
+};
+use std::{fs::File, path::PathBuf};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Creates the students in a roster CSV and, given a `class_id`, enrolls them
+/// in that class, all in one transaction. Students whose email address is
+/// already in use are enrolled rather than created again. With `preview` set
+/// nothing is written and the report shows what an import would do.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn import_students_csv(
+    state: State<'_, Mutex<AppState>>,
+    file_path: PathBuf,
+    columns: RosterColumns,
+    class_id: Option<i64>,
+    preview: bool,
+) -> Result<RosterImportReport, GradifyError> {
+    let mut rows = roster::parse_roster(File::open(&file_path)?, &columns)?;
+    let state = state.lock().await;
+
+    if let Some(class_id) = class_id {
+        sqlx::query_scalar::<_, i64>("SELECT ID FROM CLASSES WHERE ID = ?")
+            .bind(class_id)
+            .fetch_optional(&state.db.pool)
+            .await?
+            .ok_or_else(|| GradifyError::not_found(format!("Class {class_id}")))?;
+    }
+
+    let mut tx = state.db.pool.begin().await?;
+
+    for row in &mut rows {
+        let Some(email) = row.email.as_deref() else {
+            continue;
+        };
+        if row.status == RosterRowStatus::Invalid {
+            continue;
+        }
+        let Some(student_id) =
+            sqlx::query_scalar::<_, i64>("SELECT ID FROM STUDENTS WHERE LOWER(EMAIL) = LOWER(?)")
+                .bind(email)
+                .fetch_optional(&mut *tx)
+                .await?
+        else {
+            continue;
+        };
+
+        row.student_id = Some(student_id);
+        row.status = RosterRowStatus::Existing;
+        if let Some(class_id) = class_id {
+            let enrolled = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS (SELECT 1 FROM STUDENT_CLASSES WHERE STUDENT_ID = ? AND CLASS_ID = ?)",
+            )
+            .bind(student_id)
+            .bind(class_id)
+            .fetch_one(&mut *tx)
+            .await?;
+            if enrolled {
+                row.status = RosterRowStatus::AlreadyEnrolled;
+            }
+        }
+    }
+
+    if preview || rows.iter().any(|r| r.status == RosterRowStatus::Invalid) {
+        tx.rollback().await?;
+        return Ok(RosterImportReport {
+            committed: false,
+            rows,
+        });
+    }
+
+    for row in &mut rows {
+        if row.status == RosterRowStatus::New {
+            let created = sqlx::query(
+                "INSERT INTO STUDENTS (FIRST_NAME, LAST_NAME, EMAIL)
+                 VALUES (?, ?, ?)",
+            )
+            .bind(&row.first_name)
+            .bind(&row.last_name)
+            .bind(&row.email)
+            .execute(&mut *tx)
+            .await;
+            match created {
+                Ok(result) => row.student_id = Some(result.last_insert_rowid()),
+                Err(e) => {
+                    row.error = Some(GradifyError::from(e).to_string());
+                    continue;
+                }
+            }
+        }
+
+        if let (Some(class_id), Some(student_id)) = (class_id, row.student_id) {
+            if row.status != RosterRowStatus::AlreadyEnrolled {
+                if let Err(e) = insert_enrollment(&mut tx, student_id, class_id).await {
+                    row.error = Some(GradifyError::from(e).to_string());
+                }
+            }
+        }
+    }
+
+    let committed = rows.iter().all(|r| r.error.is_none());
+    if committed {
+        tx.commit().await?;
+    } else {
+        tx.rollback().await?;
+        for row in rows.iter_mut().filter(|r| r.status == RosterRowStatus::New) {
+            row.student_id = None;
+        }
+    }
+
+    Ok(RosterImportReport { committed, rows })
+}
@@ -0,0 +1,98 @@
+use crate::{
+    commands::overall_grades::{
+        calculate_class_grades, load_assignment, load_class_grades, load_class_student_ids,
+        load_grading_policy,
+    },
+    database::models::{AssignmentStatistics, ClassStatistics, ScoreDistribution},
+    error::GradifyError,
+    grading, statistics, validation, AppState,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+const DEFAULT_BUCKET_WIDTH: f64 = 10.0;
+
+/// Narrower buckets would make for an unreadable number of them.
+const MIN_BUCKET_WIDTH: f64 = 1.0;
+
+fn validate_bucket_width(bucket_width: Option<f64>) -> Result<f64, GradifyError> {
+    validation::number_in(
+        "bucket_width",
+        bucket_width.unwrap_or(DEFAULT_BUCKET_WIDTH),
+        MIN_BUCKET_WIDTH..=100.0,
+    )
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_class_statistics(
+    state: State<'_, Mutex<AppState>>,
+    class_id: i64,
+) -> Result<ClassStatistics, GradifyError> {
+    let state = state.lock().await;
+
+    let policy = load_grading_policy(&state.db.pool, class_id).await?;
+    let overall_grades = calculate_class_grades(&state.db.pool, class_id).await?;
+
+    Ok(statistics::class_statistics(&policy, &overall_grades))
+}
+
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_assignment_statistics(
+    state: State<'_, Mutex<AppState>>,
+    assignment_id: i64,
+) -> Result<AssignmentStatistics, GradifyError> {
+    let state = state.lock().await;
+
+    let assignment = load_assignment(&state.db.pool, assignment_id).await?;
+
+    let policy = load_grading_policy(&state.db.pool, assignment.class_id).await?;
+    let student_ids = load_class_student_ids(&state.db.pool, assignment.class_id).await?;
+    let grades = load_class_grades(&state.db.pool, assignment.class_id).await?;
+
+    Ok(statistics::assignment_statistics(
+        &policy,
+        &student_ids,
+        &assignment,
+        &grades,
+    ))
+}
+
+/// Histogram of the overall percentages of a class, with where each student
+/// stands. `bucket_width` defaults to 10 percentage points.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_class_distribution(
+    state: State<'_, Mutex<AppState>>,
+    class_id: i64,
+    bucket_width: Option<f64>,
+) -> Result<ScoreDistribution, GradifyError> {
+    let bucket_width = validate_bucket_width(bucket_width)?;
+    let state = state.lock().await;
+
+    let overall_grades = calculate_class_grades(&state.db.pool, class_id).await?;
+    let percentages: Vec<(i64, f64)> = overall_grades
+        .iter()
+        .map(|g| (g.student_id, g.percentage))
+        .collect();
+
+    Ok(statistics::score_distribution(&percentages, bucket_width))
+}
+
+/// Histogram of the percentages scored on an assignment, with where each
+/// student stands. `bucket_width` defaults to 10 percentage points.
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn get_assignment_distribution(
+    state: State<'_, Mutex<AppState>>,
+    assignment_id: i64,
+    bucket_width: Option<f64>,
+) -> Result<ScoreDistribution, GradifyError> {
+    let bucket_width = validate_bucket_width(bucket_width)?;
+    let state = state.lock().await;
+
+    let assignment = load_assignment(&state.db.pool, assignment_id).await?;
+    let policy = load_grading_policy(&state.db.pool, assignment.class_id).await?;
+    let student_ids = load_class_student_ids(&state.db.pool, assignment.class_id).await?;
+    let grades = load_class_grades(&state.db.pool, assignment.class_id).await?;
+
+    let percentages = grading::assignment_percentages(&policy, &student_ids, &assignment, &grades);
+    Ok(statistics::score_distribution(&percentages, bucket_width))
+}
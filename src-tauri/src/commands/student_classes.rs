@@ -1,48 +1,55 @@
-use crate::{database::models::StudentClass, AppState};
+use crate::{database::models::StudentClass, error::GradifyError, AppState};
+use sqlx::SqliteConnection;
 use tauri::State;
 use tokio::sync::Mutex;
 
-#[tauri::command(async, rename_all = "snake_case")]
-pub async fn enroll_student(
-    state: State<'_, Mutex<AppState>>,
+/// Enrolls a student in a class. Shared by `enroll_student` and roster
+/// imports, which enroll inside their own transaction.
+pub(crate) async fn insert_enrollment(
+    conn: &mut SqliteConnection,
     student_id: i64,
     class_id: i64,
-) -> Result<StudentClass, String> {
-    let state = state.lock().await;
-
+) -> Result<StudentClass, sqlx::Error> {
     sqlx::query(
         "INSERT INTO STUDENT_CLASSES (STUDENT_ID, CLASS_ID)
          VALUES (?, ?)",
     )
     .bind(student_id)
     .bind(class_id)
-    .execute(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .execute(&mut *conn)
+    .await?;
 
-    let enrollment = sqlx::query_as::<_, StudentClass>(
+    sqlx::query_as::<_, StudentClass>(
         "SELECT STUDENT_ID, CLASS_ID FROM STUDENT_CLASSES
          WHERE STUDENT_ID = ? AND CLASS_ID = ?",
     )
     .bind(student_id)
     .bind(class_id)
-    .fetch_one(&state.db.pool)
+    .fetch_one(&mut *conn)
     .await
-    .map_err(|e| e.to_string())?;
+}
 
+#[tauri::command(async, rename_all = "snake_case")]
+pub async fn enroll_student(
+    state: State<'_, Mutex<AppState>>,
+    student_id: i64,
+    class_id: i64,
+) -> Result<StudentClass, GradifyError> {
+    let state = state.lock().await;
+    let mut conn = state.db.pool.acquire().await?;
+    let enrollment = insert_enrollment(&mut conn, student_id, class_id).await?;
     Ok(enrollment)
 }
 
 #[tauri::command(async, rename_all = "snake_case")]
 pub async fn get_enrollments(
     state: State<'_, Mutex<AppState>>,
-) -> Result<Vec<StudentClass>, String> {
+) -> Result<Vec<StudentClass>, GradifyError> {
     let state = state.lock().await;
     let enrollments =
         sqlx::query_as::<_, StudentClass>("SELECT STUDENT_ID, CLASS_ID FROM STUDENT_CLASSES")
             .fetch_all(&state.db.pool)
-            .await
-            .map_err(|e| e.to_string())?;
+            .await?;
     Ok(enrollments)
 }
 
@@ -51,13 +58,12 @@ pub async fn unenroll_student(
     state: State<'_, Mutex<AppState>>,
     student_id: i64,
     class_id: i64,
-) -> Result<(), String> {
+) -> Result<(), GradifyError> {
     let state = state.lock().await;
     sqlx::query("DELETE FROM STUDENT_CLASSES WHERE STUDENT_ID = ? AND CLASS_ID = ?")
         .bind(student_id)
         .bind(class_id)
         .execute(&state.db.pool)
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
     Ok(())
 }
@@ -1,4 +1,4 @@
-use crate::{database::models::Student, AppState};
+use crate::{database::models::Student, error::GradifyError, validation, AppState};
 use tauri::State;
 use tokio::sync::Mutex;
 
@@ -8,7 +8,10 @@ pub async fn create_student(
     first_name: String,
     last_name: String,
     email: Option<String>,
-) -> Result<Student, String> {
+) -> Result<Student, GradifyError> {
+    let first_name = validation::required_text("first_name", &first_name)?;
+    let last_name = validation::required_text("last_name", &last_name)?;
+    let email = validation::email(email.as_deref())?;
     let state = state.lock().await;
 
     let result = sqlx::query(
@@ -19,8 +22,7 @@ pub async fn create_student(
     .bind(last_name.clone())
     .bind(&email)
     .execute(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
     let id = result.last_insert_rowid();
 
@@ -29,34 +31,36 @@ pub async fn create_student(
     )
     .bind(id)
     .fetch_one(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
     Ok(student)
 }
 
 #[tauri::command(async, rename_all = "snake_case")]
-pub async fn get_student(state: State<'_, Mutex<AppState>>, id: i64) -> Result<Student, String> {
+pub async fn get_student(
+    state: State<'_, Mutex<AppState>>,
+    id: i64,
+) -> Result<Student, GradifyError> {
     let state = state.lock().await;
     let student = sqlx::query_as::<_, Student>(
         "SELECT ID, FIRST_NAME, LAST_NAME, EMAIL FROM STUDENTS WHERE ID = ?",
     )
     .bind(id)
     .fetch_one(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
     Ok(student)
 }
 
 #[tauri::command(async, rename_all = "snake_case")]
-pub async fn get_all_students(state: State<'_, Mutex<AppState>>) -> Result<Vec<Student>, String> {
+pub async fn get_all_students(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Vec<Student>, GradifyError> {
     let state = state.lock().await;
     let students =
         sqlx::query_as::<_, Student>("SELECT ID, FIRST_NAME, LAST_NAME, EMAIL FROM STUDENTS")
             .fetch_all(&state.db.pool)
-            .await
-            .map_err(|e| e.to_string())?;
+            .await?;
 
     Ok(students)
 }
@@ -68,10 +72,13 @@ pub async fn update_student(
     first_name: String,
     last_name: String,
     email: Option<String>,
-) -> Result<Student, String> {
+) -> Result<Student, GradifyError> {
+    let first_name = validation::required_text("first_name", &first_name)?;
+    let last_name = validation::required_text("last_name", &last_name)?;
+    let email = validation::email(email.as_deref())?;
     let state = state.lock().await;
 
-    sqlx::query(
+    let result = sqlx::query(
         "UPDATE STUDENTS
          SET FIRST_NAME = ?, LAST_NAME = ?, EMAIL = ?
          WHERE ID = ?",
@@ -81,28 +88,32 @@ pub async fn update_student(
     .bind(&email)
     .bind(id)
     .execute(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(GradifyError::not_found(format!("Student {id}")));
+    }
 
     let student = sqlx::query_as::<_, Student>(
         "SELECT ID, FIRST_NAME, LAST_NAME, EMAIL FROM STUDENTS WHERE ID = ?",
     )
     .bind(id)
     .fetch_one(&state.db.pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
     Ok(student)
 }
 
 #[tauri::command(async, rename_all = "snake_case")]
-pub async fn delete_student(state: State<'_, Mutex<AppState>>, id: i64) -> Result<(), String> {
+pub async fn delete_student(
+    state: State<'_, Mutex<AppState>>,
+    id: i64,
+) -> Result<(), GradifyError> {
     let state = state.lock().await;
     sqlx::query("DELETE FROM STUDENTS WHERE ID = ?")
         .bind(id)
         .execute(&state.db.pool)
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
 
     Ok(())
 }
@@ -0,0 +1,39 @@
+use crate::error::GradifyError;
+use std::io::Read;
+
+/// A reader for CSV files with a header row, as exported by spreadsheets and
+/// other school systems. Rows may differ in length and fields are trimmed.
+pub fn reader<R: Read>(reader: R) -> csv::Reader<R> {
+    csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(reader)
+}
+
+/// Finds the column headed `name`, ignoring case.
+pub fn column(headers: &csv::StringRecord, name: &str) -> Result<usize, GradifyError> {
+    headers
+        .iter()
+        .position(|header| header.eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| {
+            GradifyError::validation("columns", format!("The file has no '{name}' column"))
+        })
+}
+
+pub fn unreadable(error: csv::Error) -> GradifyError {
+    GradifyError::validation(
+        "file_path",
+        format!("The CSV file could not be read: {error}"),
+    )
+}
+
+/// Text for a cell of an exported CSV file. Text a spreadsheet would take for
+/// a formula, starting with `=`, `+`, `-`, `@`, a tab or a carriage return, is
+/// prefixed with `'` so it shows as written.
+pub fn text_cell(text: &str) -> String {
+    if text.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{text}")
+    } else {
+        text.to_string()
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    database::{db, models::ArchiveManifest},
+    error::GradifyError,
+};
+use chrono::Local;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use std::{
+    fs::{self, File},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+use zip::{write::SimpleFileOptions, ZipArchive, ZipWriter};
+
+/// Bumped when the layout of the archive itself changes, not the schema of
+/// the database inside it.
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_ENTRY: &str = "manifest.json";
+const DATABASE_ENTRY: &str = "gradify.sqlite";
+
+/// Writes a `.gradify` archive holding a snapshot of the whole database and a
+/// manifest describing it.
+pub async fn export_archive(
+    pool: &SqlitePool,
+    archive_path: &Path,
+) -> Result<ArchiveManifest, GradifyError> {
+    let snapshot_path = temp_path("export");
+    sqlx::query("VACUUM INTO ?")
+        .bind(snapshot_path.to_string_lossy().into_owned())
+        .execute(pool)
+        .await?;
+    let database = fs::read(&snapshot_path);
+    fs::remove_file(&snapshot_path)?;
+    let database = database?;
+
+    let manifest = ArchiveManifest {
+        format_version: ARCHIVE_FORMAT_VERSION,
+        schema_version: db::schema_version(pool).await?,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: Local::now().naive_local(),
+        database_sha256: sha256(&database),
+    };
+
+    let mut zip = ZipWriter::new(File::create(archive_path)?);
+    zip.start_file(MANIFEST_ENTRY, SimpleFileOptions::default())
+        .map_err(invalid_archive)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest).map_err(invalid_archive)?)?;
+    zip.start_file(DATABASE_ENTRY, SimpleFileOptions::default())
+        .map_err(invalid_archive)?;
+    zip.write_all(&database)?;
+    zip.finish().map_err(invalid_archive)?;
+
+    Ok(manifest)
+}
+
+/// Reads the manifest and database out of a `.gradify` archive, checking the
+/// database against its checksum and that this version of the app can open
+/// it. Older schemas are fine; they are migrated when the database is opened.
+pub fn read_archive(archive_path: &Path) -> Result<(ArchiveManifest, Vec<u8>), GradifyError> {
+    let mut zip = ZipArchive::new(File::open(archive_path)?).map_err(invalid_archive)?;
+
+    let manifest: ArchiveManifest =
+        serde_json::from_reader(zip.by_name(MANIFEST_ENTRY).map_err(invalid_archive)?)
+            .map_err(invalid_archive)?;
+    if manifest.format_version > ARCHIVE_FORMAT_VERSION
+        || manifest.schema_version > db::latest_schema_version()
+    {
+        return Err(GradifyError::validation(
+            "archive_path",
+            format!(
+                "The archive was made by a newer version of Gradify ({})",
+                manifest.app_version
+            ),
+        ));
+    }
+
+    let mut database = Vec::new();
+    zip.by_name(DATABASE_ENTRY)
+        .map_err(invalid_archive)?
+        .read_to_end(&mut database)?;
+    if sha256(&database) != manifest.database_sha256 {
+        return Err(GradifyError::validation(
+            "archive_path",
+            "The archive is damaged: its database does not match the checksum",
+        ));
+    }
+
+    Ok((manifest, database))
+}
+
+/// A path in the temp directory for a file that is deleted once used.
+pub fn temp_path(purpose: &str) -> PathBuf {
+    let nanos = Local::now().timestamp_nanos_opt().unwrap_or_default();
+    std::env::temp_dir().join(format!(
+        "gradify-{purpose}-{}-{nanos}.sqlite",
+        std::process::id()
+    ))
+}
+
+fn sha256(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn invalid_archive(error: impl std::fmt::Display) -> GradifyError {
+    GradifyError::validation(
+        "archive_path",
+        format!("The file is not a valid Gradify archive: {error}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_is_lowercase_hex() {
+        assert_eq!(
+            sha256(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,342 @@
+use crate::{
+    database::models::{BackupInfo, BackupReason, BackupSettings},
+    error::GradifyError,
+};
+use chrono::{Datelike, Local, NaiveDateTime, Timelike};
+use sqlx::SqlitePool;
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+const BACKUP_DIR_NAME: &str = "backups";
+const FILE_PREFIX: &str = "gradify-";
+const FILE_EXTENSION: &str = ".sqlite";
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// Backups are kept in a `backups` directory beside the database.
+pub fn backup_dir(db_path: &Path) -> PathBuf {
+    db_path.with_file_name(BACKUP_DIR_NAME)
+}
+
+/// Writes a snapshot of the database to `dir` with `VACUUM INTO`, which is
+/// safe while the database is in use.
+pub async fn create_backup(
+    pool: &SqlitePool,
+    dir: &Path,
+    reason: BackupReason,
+) -> Result<BackupInfo, GradifyError> {
+    fs::create_dir_all(dir)?;
+
+    let created_at = Local::now().naive_local().with_nanosecond(0).unwrap();
+    // A second backup for the same reason within the same second, say one
+    // taken right after a restore, is numbered rather than taking the place
+    // of the first.
+    let mut copy = 1;
+    let mut path = dir.join(file_name(created_at, reason, copy));
+    while path.exists() {
+        copy += 1;
+        path = dir.join(file_name(created_at, reason, copy));
+    }
+    sqlx::query("VACUUM INTO ?")
+        .bind(path.to_string_lossy().into_owned())
+        .execute(pool)
+        .await?;
+
+    Ok(backup_info(&path, created_at, reason)?)
+}
+
+/// Every backup in `dir`, newest first.
+pub fn list_backups(dir: &Path) -> io::Result<Vec<BackupInfo>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut backups = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let Some((created_at, reason, copy)) = entry.file_name().to_str().and_then(parse_file_name)
+        else {
+            continue;
+        };
+        backups.push((copy, backup_info(&entry.path(), created_at, reason)?));
+    }
+    // Ties, within the same second, go by copy number and then by name so
+    // pruning is stable.
+    backups.sort_by(|(a_copy, a), (b_copy, b)| {
+        (b.created_at, b_copy, &b.file_name).cmp(&(a.created_at, a_copy, &a.file_name))
+    });
+    Ok(backups.into_iter().map(|(_, backup)| backup).collect())
+}
+
+/// Deletes the startup and scheduled backups in `dir` that the retention
+/// rules no longer keep.
+pub fn prune_backups(dir: &Path, settings: &BackupSettings) -> io::Result<()> {
+    let backups = list_backups(dir)?;
+    for backup in expired_backups(&backups, settings) {
+        fs::remove_file(&backup.path)?;
+    }
+    Ok(())
+}
+
+/// Of `backups`, newest first, the startup and scheduled ones that are neither
+/// the newest of one of the `keep_daily` most recent days nor the newest of one
+/// of the `keep_weekly` most recent weeks. Backups taken before a migration or
+/// a restore, like manual ones, are kept until deleted by hand, and do not
+/// take up a day or week.
+fn expired_backups<'a>(
+    backups: &'a [BackupInfo],
+    settings: &BackupSettings,
+) -> Vec<&'a BackupInfo> {
+    let keep_daily = usize::try_from(settings.keep_daily).unwrap_or(0);
+    let keep_weekly = usize::try_from(settings.keep_weekly).unwrap_or(0);
+    let mut days = HashSet::new();
+    let mut weeks = HashSet::new();
+
+    backups
+        .iter()
+        .filter(|backup| is_rotated(backup.reason))
+        .filter(|backup| {
+            let day = backup.created_at.date();
+            let week = day.iso_week();
+            let kept_daily = days.len() < keep_daily && days.insert(day);
+            let kept_weekly = weeks.len() < keep_weekly && weeks.insert(week);
+            !kept_daily && !kept_weekly
+        })
+        .collect()
+}
+
+/// Whether backups taken for `reason` are subject to the retention rules.
+fn is_rotated(reason: BackupReason) -> bool {
+    match reason {
+        BackupReason::Startup | BackupReason::Scheduled => true,
+        BackupReason::Migration | BackupReason::Restore | BackupReason::Manual => false,
+    }
+}
+
+fn backup_info(
+    path: &Path,
+    created_at: NaiveDateTime,
+    reason: BackupReason,
+) -> io::Result<BackupInfo> {
+    Ok(BackupInfo {
+        file_name: path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        path: path.to_string_lossy().into_owned(),
+        created_at,
+        reason,
+        size_bytes: fs::metadata(path)?.len(),
+    })
+}
+
+/// `gradify-20250409-153000-startup.sqlite` for the first backup of a second,
+/// `gradify-20250409-153000-startup-2.sqlite` for the second, and so on.
+fn file_name(created_at: NaiveDateTime, reason: BackupReason, copy: u32) -> String {
+    let copy = if copy > 1 {
+        format!("-{copy}")
+    } else {
+        String::new()
+    };
+    format!(
+        "{FILE_PREFIX}{}-{}{copy}{FILE_EXTENSION}",
+        created_at.format(TIMESTAMP_FORMAT),
+        reason_tag(reason)
+    )
+}
+
+fn parse_file_name(file_name: &str) -> Option<(NaiveDateTime, BackupReason, u32)> {
+    let stem = file_name
+        .strip_prefix(FILE_PREFIX)?
+        .strip_suffix(FILE_EXTENSION)?;
+    let (rest, last) = stem.rsplit_once('-')?;
+    let (stem, copy) = match last.parse::<u32>() {
+        Ok(copy) if copy > 1 => (rest, copy),
+        _ => (stem, 1),
+    };
+    let (timestamp, tag) = stem.rsplit_once('-')?;
+    let created_at = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).ok()?;
+    let reason = [
+        BackupReason::Startup,
+        BackupReason::Scheduled,
+        BackupReason::Migration,
+        BackupReason::Restore,
+        BackupReason::Manual,
+    ]
+    .into_iter()
+    .find(|reason| reason_tag(*reason) == tag)?;
+    Some((created_at, reason, copy))
+}
+
+fn reason_tag(reason: BackupReason) -> &'static str {
+    match reason {
+        BackupReason::Startup => "startup",
+        BackupReason::Scheduled => "scheduled",
+        BackupReason::Migration => "migration",
+        BackupReason::Restore => "restore",
+        BackupReason::Manual => "manual",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(timestamp: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn backup(timestamp: &str, reason: BackupReason) -> BackupInfo {
+        let created_at = at(timestamp);
+        BackupInfo {
+            file_name: file_name(created_at, reason, 1),
+            path: String::new(),
+            created_at,
+            reason,
+            size_bytes: 0,
+        }
+    }
+
+    fn settings(keep_daily: i64, keep_weekly: i64) -> BackupSettings {
+        BackupSettings {
+            interval_hours: 6,
+            keep_daily,
+            keep_weekly,
+        }
+    }
+
+    fn names(backups: Vec<&BackupInfo>) -> Vec<&str> {
+        backups.iter().map(|b| b.file_name.as_str()).collect()
+    }
+
+    #[test]
+    fn file_names_round_trip() {
+        let created_at = at("2025-04-09 15:30");
+
+        let name = file_name(created_at, BackupReason::Migration, 1);
+        let second = file_name(created_at, BackupReason::Restore, 2);
+
+        assert_eq!(name, "gradify-20250409-153000-migration.sqlite");
+        assert_eq!(
+            parse_file_name(&name),
+            Some((created_at, BackupReason::Migration, 1))
+        );
+        assert_eq!(second, "gradify-20250409-153000-restore-2.sqlite");
+        assert_eq!(
+            parse_file_name(&second),
+            Some((created_at, BackupReason::Restore, 2))
+        );
+        assert_eq!(parse_file_name("db.sqlite"), None);
+        assert_eq!(
+            parse_file_name("gradify-20250409-153000-other.sqlite"),
+            None
+        );
+    }
+
+    #[test]
+    fn keeps_the_newest_backup_of_each_recent_day() {
+        let backups = [
+            backup("2025-04-09 18:00", BackupReason::Scheduled),
+            backup("2025-04-09 12:00", BackupReason::Scheduled),
+            backup("2025-04-08 12:00", BackupReason::Startup),
+            backup("2025-04-07 12:00", BackupReason::Startup),
+        ];
+
+        let expired = expired_backups(&backups, &settings(2, 0));
+
+        assert_eq!(
+            names(expired),
+            [
+                "gradify-20250409-120000-scheduled.sqlite",
+                "gradify-20250407-120000-startup.sqlite",
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_the_newest_backup_of_each_recent_week() {
+        let backups = [
+            backup("2025-04-09 12:00", BackupReason::Scheduled),
+            backup("2025-04-02 12:00", BackupReason::Scheduled),
+            backup("2025-04-01 12:00", BackupReason::Scheduled),
+            backup("2025-03-26 12:00", BackupReason::Scheduled),
+            backup("2025-03-19 12:00", BackupReason::Scheduled),
+        ];
+
+        let expired = expired_backups(&backups, &settings(1, 3));
+
+        assert_eq!(
+            names(expired),
+            [
+                "gradify-20250401-120000-scheduled.sqlite",
+                "gradify-20250319-120000-scheduled.sqlite",
+            ]
+        );
+    }
+
+    #[test]
+    fn manual_backups_are_never_pruned() {
+        let backups = [
+            backup("2025-04-09 12:00", BackupReason::Scheduled),
+            backup("2025-04-01 12:00", BackupReason::Manual),
+        ];
+
+        assert!(expired_backups(&backups, &settings(1, 0)).is_empty());
+    }
+
+    #[test]
+    fn migration_and_restore_backups_survive_a_later_startup_backup() {
+        let backups = [
+            backup("2025-04-09 12:01", BackupReason::Startup),
+            backup("2025-04-09 12:00", BackupReason::Migration),
+            backup("2025-04-09 11:00", BackupReason::Restore),
+            backup("2025-04-09 10:00", BackupReason::Startup),
+        ];
+
+        let expired = expired_backups(&backups, &settings(1, 1));
+
+        assert_eq!(names(expired), ["gradify-20250409-100000-startup.sqlite"]);
+    }
+
+    #[tokio::test]
+    async fn backups_in_quick_succession_each_get_a_file_of_their_own() {
+        let dir = std::env::temp_dir().join(format!("gradify-backups-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("db.sqlite");
+        let pool = SqlitePool::connect(&format!("sqlite://{}?mode=rwc", db_path.display()))
+            .await
+            .unwrap();
+        let dir = backup_dir(&db_path);
+        sqlx::query("CREATE TABLE NOTES (TEXT TEXT)")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let first = create_backup(&pool, &dir, BackupReason::Manual)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO NOTES VALUES ('after the first backup')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let second = create_backup(&pool, &dir, BackupReason::Manual)
+            .await
+            .unwrap();
+
+        assert_ne!(first.file_name, second.file_name);
+        assert_eq!(list_backups(&dir).unwrap()[0].file_name, second.file_name);
+        let copy = SqlitePool::connect(&format!("sqlite://{}", second.path))
+            .await
+            .unwrap();
+        let notes: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM NOTES")
+            .fetch_one(&copy)
+            .await
+            .unwrap();
+        assert_eq!(notes, 1);
+    }
+}
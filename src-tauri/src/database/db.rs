@@ -1,16 +1,84 @@
-use sqlx::SqlitePool;
+use crate::database::{backup, models::BackupReason};
+use crate::error::GradifyError;
+use sqlx::{migrate::Migrator, SqlitePool};
+use std::path::Path;
+use tokio::fs;
+
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
 
 pub struct Database {
     pub pool: SqlitePool,
 }
 
 impl Database {
-    pub async fn new(url: &str) -> Self {
-        let pool = SqlitePool::connect(url).await.expect("Can't connect to db");
-        sqlx::migrate!("./migrations")
-            .run(&pool)
-            .await
-            .expect("Can't run migrations");
-        Self { pool }
+    /// Opens the database file at `path`, creating it and its directory first
+    /// if they do not exist yet. The database is backed up before any new
+    /// migrations run.
+    pub async fn open(path: &Path) -> Result<Self, GradifyError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).await?;
+        }
+        if !path.exists() {
+            fs::write(path, b"").await?;
+        }
+
+        let url = format!("sqlite://{}", path.display());
+        Self::connect(&url, &backup::backup_dir(path)).await
+    }
+
+    async fn connect(url: &str, backup_dir: &Path) -> Result<Self, GradifyError> {
+        let pool = SqlitePool::connect(url).await?;
+        if let Err(e) = Self::migrate(&pool, backup_dir).await {
+            pool.close().await;
+            return Err(e);
+        }
+        Ok(Self { pool })
+    }
+
+    async fn migrate(pool: &SqlitePool, backup_dir: &Path) -> Result<(), GradifyError> {
+        if has_pending_migrations(pool).await? {
+            backup::create_backup(pool, backup_dir, BackupReason::Migration).await?;
+        }
+        MIGRATOR.run(pool).await.map_err(sqlx::Error::from)?;
+        Ok(())
+    }
+}
+
+/// The newest migration this version of the app knows about.
+pub fn latest_schema_version() -> i64 {
+    MIGRATOR
+        .iter()
+        .map(|migration| migration.version)
+        .max()
+        .unwrap_or(0)
+}
+
+/// The newest migration applied to the database behind `pool`.
+pub async fn schema_version(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT MAX(version) FROM _sqlx_migrations WHERE success = 1",
+    )
+    .fetch_one(pool)
+    .await
+    .map(Option::unwrap_or_default)
+}
+
+/// Whether a database that has been migrated before has migrations left to
+/// run. A new, empty database has nothing worth backing up.
+async fn has_pending_migrations(pool: &SqlitePool) -> Result<bool, sqlx::Error> {
+    let has_migrations_table = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')",
+    )
+    .fetch_one(pool)
+    .await?;
+    if !has_migrations_table {
+        return Ok(false);
     }
+
+    let applied = sqlx::query_scalar::<_, i64>("SELECT version FROM _sqlx_migrations")
+        .fetch_all(pool)
+        .await?;
+    Ok(MIGRATOR
+        .iter()
+        .any(|migration| !applied.contains(&migration.version)))
 }
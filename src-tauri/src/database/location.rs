@@ -0,0 +1,214 @@
+use chrono::Local;
+use std::{
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Environment variable that points the app at a database file of its own.
+pub const DATABASE_PATH_ENV: &str = "GRADIFY_DB_PATH";
+/// Command line argument that does the same, as `--db-path <file>` or
+/// `--db-path=<file>`. It wins over the environment variable.
+pub const DATABASE_PATH_ARG: &str = "--db-path";
+
+const DATABASE_FILE_NAME: &str = "db.sqlite";
+/// File in the app-data directory naming the database switched to last.
+const SAVED_PATH_FILE_NAME: &str = "database-path";
+
+/// Where the database lives when no override is given.
+pub fn default_database_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join(DATABASE_FILE_NAME)
+}
+
+/// The database path given on the command line or in the environment, if any.
+/// Relative paths are taken from the working directory.
+pub fn database_path_override(
+    args: impl Iterator<Item = OsString>,
+    env_value: Option<OsString>,
+) -> Option<PathBuf> {
+    let path = argument_value(args).or(env_value.filter(|v| !v.is_empty()))?;
+    let path = PathBuf::from(path);
+    Some(std::path::absolute(&path).unwrap_or(path))
+}
+
+fn argument_value(mut args: impl Iterator<Item = OsString>) -> Option<OsString> {
+    let prefix = format!("{DATABASE_PATH_ARG}=");
+    while let Some(arg) = args.next() {
+        if arg == DATABASE_PATH_ARG {
+            return args.next();
+        }
+        if let Some(path) = arg.to_str().and_then(|a| a.strip_prefix(&prefix)) {
+            return Some(path.into());
+        }
+    }
+    None
+}
+
+/// The database the app was last switched to, if any. Command line and
+/// environment overrides win over it.
+pub fn saved_database_path(app_data_dir: &Path) -> Option<PathBuf> {
+    let path = fs::read_to_string(app_data_dir.join(SAVED_PATH_FILE_NAME)).ok()?;
+    let path = path.trim();
+    (!path.is_empty()).then(|| PathBuf::from(path))
+}
+
+/// Remembers `path` as the database to open on later starts.
+pub fn save_database_path(app_data_dir: &Path, path: &Path) -> io::Result<()> {
+    fs::create_dir_all(app_data_dir)?;
+    fs::write(
+        app_data_dir.join(SAVED_PATH_FILE_NAME),
+        path.to_string_lossy().as_bytes(),
+    )
+}
+
+/// Where older versions kept the database: beside the executable.
+pub fn legacy_database_path() -> Option<PathBuf> {
+    let exe_path = std::env::current_exe().ok()?;
+    Some(exe_path.parent()?.join(DATABASE_FILE_NAME))
+}
+
+/// Copies the database at `legacy_path` to `path`, along with any journal
+/// files, unless `path` already exists. The legacy copy is left in place, as
+/// the install directory is often read-only. Returns whether anything was
+/// copied.
+pub fn migrate_legacy_database(legacy_path: &Path, path: &Path) -> io::Result<bool> {
+    if path.exists() || !legacy_path.is_file() {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    for suffix in ["-wal", "-shm"] {
+        let legacy_journal = with_suffix(legacy_path, suffix);
+        if legacy_journal.is_file() {
+            fs::copy(&legacy_journal, with_suffix(path, suffix))?;
+        }
+    }
+    fs::copy(legacy_path, path)?;
+    Ok(true)
+}
+
+/// Renames the database at `path`, and its journal files, out of the way so a
+/// new one can take its place. Returns where the database went, or `None` if
+/// there was nothing to move.
+pub fn set_aside(path: &Path) -> io::Result<Option<PathBuf>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let suffix = format!(".broken-{}", Local::now().format("%Y%m%d%H%M%S"));
+    for journal in ["-wal", "-shm"] {
+        let journal_path = with_suffix(path, journal);
+        if journal_path.exists() {
+            fs::rename(&journal_path, with_suffix(&journal_path, &suffix))?;
+        }
+    }
+    let set_aside_path = with_suffix(path, &suffix);
+    fs::rename(path, &set_aside_path)?;
+    Ok(Some(set_aside_path))
+}
+
+/// Deletes the journal files of the database at `path`. Only safe once the
+/// database is closed and is about to be replaced.
+pub fn remove_journals(path: &Path) -> io::Result<()> {
+    for journal in ["-wal", "-shm"] {
+        match fs::remove_file(with_suffix(path, journal)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = OsString> {
+        args.iter()
+            .map(OsString::from)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn argument_wins_over_environment() {
+        let path = database_path_override(
+            args(&["gradify", "--db-path", "/tmp/a.sqlite"]),
+            Some("/tmp/b.sqlite".into()),
+        );
+
+        assert_eq!(path, Some(PathBuf::from("/tmp/a.sqlite")));
+    }
+
+    #[test]
+    fn argument_may_use_an_equals_sign() {
+        let path = database_path_override(args(&["gradify", "--db-path=/tmp/a.sqlite"]), None);
+
+        assert_eq!(path, Some(PathBuf::from("/tmp/a.sqlite")));
+    }
+
+    #[test]
+    fn environment_is_used_without_an_argument() {
+        let path = database_path_override(args(&["gradify"]), Some("/tmp/b.sqlite".into()));
+
+        assert_eq!(path, Some(PathBuf::from("/tmp/b.sqlite")));
+        assert_eq!(
+            database_path_override(args(&["gradify"]), Some("".into())),
+            None
+        );
+    }
+
+    #[test]
+    fn saved_database_path_round_trips() {
+        let dir = std::env::temp_dir().join(format!("gradify-saved-path-{}", std::process::id()));
+
+        assert_eq!(saved_database_path(&dir), None);
+        save_database_path(&dir, Path::new("/tmp/profile.sqlite")).unwrap();
+
+        assert_eq!(
+            saved_database_path(&dir),
+            Some(PathBuf::from("/tmp/profile.sqlite"))
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn legacy_database_is_copied_once() {
+        let dir = std::env::temp_dir().join(format!("gradify-location-{}", std::process::id()));
+        let legacy_path = dir.join("legacy").join(DATABASE_FILE_NAME);
+        let path = dir.join("data").join(DATABASE_FILE_NAME);
+        fs::create_dir_all(legacy_path.parent().unwrap()).unwrap();
+        fs::write(&legacy_path, b"legacy").unwrap();
+
+        assert!(migrate_legacy_database(&legacy_path, &path).unwrap());
+        fs::write(&legacy_path, b"changed").unwrap();
+        assert!(!migrate_legacy_database(&legacy_path, &path).unwrap());
+
+        assert_eq!(fs::read(&path).unwrap(), b"legacy");
+        assert!(legacy_path.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn set_aside_moves_the_database_away() {
+        let dir = std::env::temp_dir().join(format!("gradify-set-aside-{}", std::process::id()));
+        let path = dir.join(DATABASE_FILE_NAME);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, b"broken").unwrap();
+
+        let moved = set_aside(&path).unwrap().unwrap();
+
+        assert!(!path.exists());
+        assert_eq!(fs::read(&moved).unwrap(), b"broken");
+        assert_eq!(set_aside(&path).unwrap(), None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
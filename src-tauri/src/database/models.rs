@@ -14,6 +14,13 @@ pub struct Student {
     pub email: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+pub enum GradingMode {
+    PercentAverage,
+    TotalPoints,
+    WeightedCategories,
+}
+
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Class {
     #[sqlx(rename = "ID")]
@@ -22,6 +29,30 @@ pub struct Class {
     pub class_name: String,
     #[sqlx(rename = "DESCRIPTION")]
     pub description: Option<String>,
+    #[sqlx(rename = "GRADING_MODE")]
+    pub grading_mode: GradingMode,
+    #[sqlx(rename = "GRADING_SCALE_ID")]
+    pub grading_scale_id: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct GradingScale {
+    #[sqlx(rename = "ID")]
+    pub id: i64,
+    #[sqlx(rename = "SCALE_NAME")]
+    pub scale_name: String,
+    #[sqlx(rename = "IS_DEFAULT")]
+    pub is_default: bool,
+    #[sqlx(skip)]
+    pub thresholds: Vec<GradeThreshold>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct GradeThreshold {
+    #[sqlx(rename = "LETTER_GRADE")]
+    pub letter_grade: String,
+    #[sqlx(rename = "MIN_PERCENTAGE")]
+    pub min_percentage: f64,
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
@@ -32,6 +63,30 @@ pub struct StudentClass {
     pub class_id: i64,
 }
 
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Category {
+    #[sqlx(rename = "ID")]
+    pub id: i64,
+    #[sqlx(rename = "CLASS_ID")]
+    pub class_id: i64,
+    #[sqlx(rename = "CATEGORY_NAME")]
+    pub category_name: String,
+    #[sqlx(rename = "WEIGHT")]
+    pub weight: Option<f64>,
+    #[sqlx(rename = "DROP_LOWEST")]
+    pub drop_lowest: i64,
+    #[sqlx(rename = "DROP_HIGHEST")]
+    pub drop_highest: i64,
+}
+
+/// Extra credit assignments add to the points earned without adding to the
+/// points possible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+pub enum AssignmentKind {
+    Regular,
+    ExtraCredit,
+}
+
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Assignment {
     #[sqlx(rename = "ID")]
@@ -40,32 +95,383 @@ pub struct Assignment {
     pub class_id: i64,
     #[sqlx(rename = "ASSIGNMENT_NAME")]
     pub assignment_name: String,
-    #[sqlx(rename = "ASSIGNMENT_TYPE")]
-    pub assignment_type: String,
+    #[sqlx(rename = "CATEGORY_ID")]
+    pub category_id: i64,
+    #[sqlx(rename = "CATEGORY_NAME")]
+    pub category_name: String,
     #[sqlx(rename = "MAXIMUM_SCORE")]
     pub maximum_score: f64,
     #[sqlx(rename = "DUE_DATE")]
     pub due_date: Option<NaiveDateTime>,
+    #[sqlx(rename = "ALLOWS_EXTRA_CREDIT")]
+    pub allows_extra_credit: bool,
+    #[sqlx(rename = "ASSIGNMENT_KIND")]
+    pub assignment_kind: AssignmentKind,
 }
 
-#[derive(Debug, Serialize, Deserialize, FromRow)]
+/// Excused and incomplete work is left out of the overall grade, missing work
+/// counts as a zero.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+pub enum GradeStatus {
+    #[default]
+    Graded,
+    Excused,
+    Missing,
+    Incomplete,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Grade {
     #[sqlx(rename = "STUDENT_ID")]
     pub student_id: i64,
     #[sqlx(rename = "ASSIGNMENT_ID")]
     pub assignment_id: i64,
     #[sqlx(rename = "SCORE")]
-    pub score: f64,
+    pub score: Option<f64>,
+    #[sqlx(rename = "STATUS")]
+    #[serde(default)]
+    pub status: GradeStatus,
+    #[sqlx(rename = "SUBMITTED_AT")]
+    pub submitted_at: Option<NaiveDateTime>,
+}
+
+/// Outcome of one row of a bulk grade save. `error` is `None` for rows that
+/// were, or would have been, saved.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BulkGradeResult {
+    pub student_id: i64,
+    pub assignment_id: i64,
+    pub error: Option<String>,
+}
+
+/// Rows of a bulk grade save in the order they were given. Nothing is
+/// committed unless every row succeeds.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BulkGradeReport {
+    pub committed: bool,
+    pub results: Vec<BulkGradeResult>,
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
-pub struct OverallGrade {
+pub struct LatePolicy {
+    #[sqlx(rename = "ID")]
+    pub id: i64,
+    #[sqlx(rename = "CLASS_ID")]
+    pub class_id: i64,
+    #[sqlx(rename = "ASSIGNMENT_ID")]
+    pub assignment_id: Option<i64>,
+    #[sqlx(rename = "PENALTY_PER_DAY")]
+    pub penalty_per_day: f64,
+    #[sqlx(rename = "MAX_PENALTY")]
+    pub max_penalty: Option<f64>,
+    #[sqlx(rename = "GRACE_PERIOD_HOURS")]
+    pub grace_period_hours: f64,
+    #[sqlx(rename = "ZERO_AFTER_DAYS")]
+    pub zero_after_days: Option<i64>,
+}
+
+/// Extra time a student is entitled to, either in one class or, without a
+/// class, in all of them. Only `extension_days` moves due dates;
+/// `time_multiplier`, such as 1.5 for time and a half on timed work, is stored
+/// for reference only and nothing in grading uses it.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct StudentAccommodation {
+    #[sqlx(rename = "ID")]
+    pub id: i64,
     #[sqlx(rename = "STUDENT_ID")]
     pub student_id: i64,
     #[sqlx(rename = "CLASS_ID")]
+    pub class_id: Option<i64>,
+    #[sqlx(rename = "EXTENSION_DAYS")]
+    pub extension_days: i64,
+    #[sqlx(rename = "TIME_MULTIPLIER")]
+    pub time_multiplier: f64,
+    #[sqlx(rename = "NOTES")]
+    pub notes: Option<String>,
+}
+
+/// A due date granted to one student for one assignment.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct DueDateOverride {
+    #[sqlx(rename = "STUDENT_ID")]
+    pub student_id: i64,
+    #[sqlx(rename = "ASSIGNMENT_ID")]
+    pub assignment_id: i64,
+    #[sqlx(rename = "DUE_DATE")]
+    pub due_date: NaiveDateTime,
+    #[sqlx(rename = "REASON")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpcomingDeadline {
+    pub student_id: i64,
+    pub assignment_id: i64,
+    pub assignment_name: String,
     pub class_id: i64,
-    #[sqlx(rename = "PERCENTAGE")]
+    pub due_date: Option<NaiveDateTime>,
+    pub effective_due_date: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LateSubmission {
+    pub student_id: i64,
+    pub assignment_id: i64,
+    pub due_date: NaiveDateTime,
+    pub submitted_at: NaiveDateTime,
+    pub days_late: i64,
+    pub penalty_percentage: f64,
+    pub raw_score: Option<f64>,
+    pub adjusted_score: Option<f64>,
+}
+
+/// How a curve moves an assignment's scores. `FlatAdd` adds its value in
+/// points, `TargetMean` shifts every score so the mean lands on its value as a
+/// percentage of the maximum score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+pub enum CurveKind {
+    FlatAdd,
+    ScaleToHighest,
+    SquareRoot,
+    TargetMean,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct AssignmentCurve {
+    #[sqlx(rename = "ASSIGNMENT_ID")]
+    pub assignment_id: i64,
+    #[sqlx(rename = "CURVE_KIND")]
+    pub curve_kind: CurveKind,
+    #[sqlx(rename = "CURVE_VALUE")]
+    pub curve_value: Option<f64>,
+    #[sqlx(rename = "APPLIED_AT")]
+    pub applied_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CurvedScore {
+    pub student_id: i64,
+    pub status: GradeStatus,
+    pub raw_score: Option<f64>,
+    pub curved_score: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategorySubtotal {
+    pub category_id: i64,
+    pub category_name: String,
+    pub weight: Option<f64>,
+    pub graded_count: usize,
+    pub points_earned: f64,
+    pub points_possible: f64,
+    pub percentage: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OverallGrade {
+    pub student_id: i64,
+    pub class_id: i64,
+    pub grading_mode: GradingMode,
     pub percentage: f64,
-    #[sqlx(rename = "LETTER_GRADE")]
     pub letter_grade: String,
+    pub category_subtotals: Vec<CategorySubtotal>,
+    pub dropped_grades: Vec<Grade>,
+}
+
+/// Summary of a set of percentages. Everything but `count` is `None` when the
+/// set is empty.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScoreSummary {
+    pub count: usize,
+    pub mean: Option<f64>,
+    pub median: Option<f64>,
+    pub std_dev: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub first_quartile: Option<f64>,
+    pub third_quartile: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LetterGradeCount {
+    pub letter_grade: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryStatistics {
+    pub category_id: i64,
+    pub category_name: String,
+    pub summary: ScoreSummary,
+}
+
+/// Statistics of the overall percentages of a class's students.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClassStatistics {
+    pub class_id: i64,
+    pub summary: ScoreSummary,
+    pub letter_distribution: Vec<LetterGradeCount>,
+    pub categories: Vec<CategoryStatistics>,
+}
+
+/// Statistics of the percentages scored on one assignment.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AssignmentStatistics {
+    pub assignment_id: i64,
+    pub class_id: i64,
+    pub summary: ScoreSummary,
+    pub letter_distribution: Vec<LetterGradeCount>,
+}
+
+/// Percentages from `lower` up to, but not including, `upper`. The last
+/// bucket of a histogram also holds its upper bound.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HistogramBucket {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: usize,
+}
+
+/// Where one student sits among everyone in the same distribution.
+/// `z_score` is `None` when all percentages are the same.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StudentStanding {
+    pub student_id: i64,
+    pub percentage: f64,
+    pub percentile_rank: f64,
+    pub z_score: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScoreDistribution {
+    pub bucket_width: f64,
+    pub buckets: Vec<HistogramBucket>,
+    pub standings: Vec<StudentStanding>,
+}
+
+/// Why a backup was taken. Manual backups are never pruned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BackupReason {
+    Startup,
+    Scheduled,
+    Migration,
+    Restore,
+    Manual,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupInfo {
+    pub file_name: String,
+    pub path: String,
+    pub created_at: NaiveDateTime,
+    pub reason: BackupReason,
+    pub size_bytes: u64,
+}
+
+/// `interval_hours` of 0 turns scheduled backups off. Automatic backups are
+/// kept for the `keep_daily` most recent days and `keep_weekly` most recent
+/// weeks that have one.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct BackupSettings {
+    #[sqlx(rename = "INTERVAL_HOURS")]
+    pub interval_hours: i64,
+    #[sqlx(rename = "KEEP_DAILY")]
+    pub keep_daily: i64,
+    #[sqlx(rename = "KEEP_WEEKLY")]
+    pub keep_weekly: i64,
+}
+
+/// Describes the database inside a `.gradify` archive. `schema_version` is the
+/// newest migration applied to it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub format_version: u32,
+    pub schema_version: i64,
+    pub app_version: String,
+    pub exported_at: NaiveDateTime,
+    pub database_sha256: String,
+}
+
+/// Which CSV columns, by header, hold a roster's names and email addresses.
+/// A full name column may be "Last, First" or "First Last".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum RosterColumns {
+    SeparateNames {
+        first_name: String,
+        last_name: String,
+        email: Option<String>,
+    },
+    FullName {
+        full_name: String,
+        email: Option<String>,
+    },
+}
+
+/// `Existing` rows match a student by email and are enrolled rather than
+/// created again; `AlreadyEnrolled` rows need nothing done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RosterRowStatus {
+    New,
+    Existing,
+    AlreadyEnrolled,
+    Invalid,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RosterImportRow {
+    pub line: u64,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: Option<String>,
+    pub status: RosterRowStatus,
+    pub student_id: Option<i64>,
+    pub error: Option<String>,
+}
+
+/// Rows of a roster import in file order. Nothing is committed unless every
+/// row is valid.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RosterImportReport {
+    pub committed: bool,
+    pub rows: Vec<RosterImportRow>,
+}
+
+/// How a gradebook export renders grades. Without `status_marks`, excused,
+/// missing and incomplete work is left blank rather than marked "EX", "M" or
+/// "I".
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GradebookExportOptions {
+    pub status_marks: bool,
+    pub max_points_row: bool,
+}
+
+/// What a grade import does with a row: `New` and `Replaces` rows are saved,
+/// `Kept` rows leave a grade that already has a score alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GradeImportStatus {
+    New,
+    Replaces,
+    Kept,
+    Unmatched,
+    Invalid,
+}
+
+/// `student` is the email address or student ID as written in the file.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GradeImportRow {
+    pub line: u64,
+    pub student: String,
+    pub student_id: Option<i64>,
+    pub score: Option<f64>,
+    pub status: GradeImportStatus,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GradeImportReport {
+    pub committed: bool,
+    pub imported: usize,
+    pub rows: Vec<GradeImportRow>,
 }
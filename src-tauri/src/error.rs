@@ -0,0 +1,195 @@
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use std::fmt;
+
+/// Error returned by every command. It reaches the frontend as
+/// `{ kind, field, message }`, so the UI can tell cases apart and point at the
+/// offending field without matching on messages.
+#[derive(Debug)]
+pub enum GradifyError {
+    /// What could not be found, e.g. "Student 4".
+    NotFound(String),
+    /// A value that has to be unique is already taken.
+    Conflict {
+        field: String,
+    },
+    Validation {
+        field: String,
+        message: String,
+    },
+    ScoreExceedsMax,
+    Database(sqlx::Error),
+    Io(std::io::Error),
+}
+
+impl GradifyError {
+    pub fn not_found(what: impl Into<String>) -> Self {
+        Self::NotFound(what.into())
+    }
+
+    pub fn validation(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Validation {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "NotFound",
+            Self::Conflict { .. } => "Conflict",
+            Self::Validation { .. } => "Validation",
+            Self::ScoreExceedsMax => "ScoreExceedsMax",
+            Self::Database(_) => "Database",
+            Self::Io(_) => "Io",
+        }
+    }
+
+    fn field(&self) -> Option<&str> {
+        match self {
+            Self::Conflict { field } | Self::Validation { field, .. } => Some(field),
+            Self::ScoreExceedsMax => Some("score"),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for GradifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(what) => write!(f, "{what} not found"),
+            Self::Conflict { field } => write!(f, "A record with the same {field} already exists"),
+            Self::Validation { message, .. } => f.write_str(message),
+            Self::ScoreExceedsMax => {
+                f.write_str("Score cannot exceed the assignment maximum score")
+            }
+            Self::Database(e) => write!(f, "{e}"),
+            Self::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for GradifyError {}
+
+impl Serialize for GradifyError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("GradifyError", 3)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("field", &self.field())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+impl From<sqlx::Error> for GradifyError {
+    fn from(error: sqlx::Error) -> Self {
+        match &error {
+            sqlx::Error::RowNotFound => Self::not_found("Record"),
+            sqlx::Error::Database(db_error) => {
+                constraint_error(db_error.message()).unwrap_or(Self::Database(error))
+            }
+            _ => Self::Database(error),
+        }
+    }
+}
+
+impl From<std::io::Error> for GradifyError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Maps the messages SQLite gives for constraint and trigger failures.
+fn constraint_error(message: &str) -> Option<GradifyError> {
+    if let Some(columns) = message.strip_prefix("UNIQUE constraint failed: ") {
+        return Some(GradifyError::Conflict {
+            field: field_names(columns),
+        });
+    }
+    if let Some(columns) = message.strip_prefix("NOT NULL constraint failed: ") {
+        let field = field_names(columns);
+        let message = format!("The {field} is required");
+        return Some(GradifyError::Validation { field, message });
+    }
+    if let Some(expression) = message.strip_prefix("CHECK constraint failed: ") {
+        let column: String = expression
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+            .collect();
+        let field = column.to_lowercase();
+        let message = format!("The {field} is not valid");
+        return Some(GradifyError::Validation { field, message });
+    }
+    match message {
+        "FOREIGN KEY constraint failed" => Some(GradifyError::not_found("Referenced record")),
+        "Score cannot exceed the assignment maximum score" => Some(GradifyError::ScoreExceedsMax),
+        "Category does not belong to the assignment class" => {
+            Some(GradifyError::validation("category_id", message))
+        }
+        "The default grading scale cannot be deleted" => {
+            Some(GradifyError::validation("is_default", message))
+        }
+        _ => None,
+    }
+}
+
+/// `STUDENTS.EMAIL` becomes `email`; several columns are joined with commas.
+fn field_names(columns: &str) -> String {
+    columns
+        .split(", ")
+        .map(|column| column.rsplit('.').next().unwrap_or(column).to_lowercase())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unique_violations_name_the_field() {
+        let error = constraint_error("UNIQUE constraint failed: STUDENTS.EMAIL").unwrap();
+
+        assert_eq!(error.field(), Some("email"));
+        assert_eq!(error.kind(), "Conflict");
+    }
+
+    #[test]
+    fn composite_unique_violations_name_every_field() {
+        let error =
+            constraint_error("UNIQUE constraint failed: GRADES.STUDENT_ID, GRADES.ASSIGNMENT_ID")
+                .unwrap();
+
+        assert_eq!(error.field(), Some("student_id, assignment_id"));
+    }
+
+    #[test]
+    fn check_violations_name_the_column() {
+        let error = constraint_error("CHECK constraint failed: PENALTY_PER_DAY >= 0").unwrap();
+
+        assert_eq!(error.kind(), "Validation");
+        assert_eq!(error.field(), Some("penalty_per_day"));
+    }
+
+    #[test]
+    fn score_trigger_maps_to_score_exceeds_max() {
+        let error = constraint_error("Score cannot exceed the assignment maximum score").unwrap();
+
+        assert_eq!(error.kind(), "ScoreExceedsMax");
+    }
+
+    #[test]
+    fn errors_serialise_with_kind_field_and_message() {
+        let error = GradifyError::validation("score", "A graded grade needs a score");
+
+        let json = serde_json::to_value(&error).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "kind": "Validation",
+                "field": "score",
+                "message": "A graded grade needs a score",
+            })
+        );
+    }
+}
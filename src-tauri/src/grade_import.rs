@@ -0,0 +1,157 @@
+use crate::{
+    csv_file,
+    database::models::{GradeImportRow, GradeImportStatus, GradeStatus, Student},
+    error::GradifyError,
+};
+use std::{collections::HashSet, io::Read};
+
+/// Reads the student and score of every row of a grade CSV with a header row.
+/// Rows without a usable score are marked `Invalid`; every other row is
+/// `Unmatched` until matched to a student. Blank rows are skipped.
+pub fn parse_grade_rows(
+    reader: impl Read,
+    student_column: &str,
+    score_column: &str,
+) -> Result<Vec<GradeImportRow>, GradifyError> {
+    let mut csv = csv_file::reader(reader);
+    let headers = csv.headers().map_err(csv_file::unreadable)?.clone();
+    let student_column = csv_file::column(&headers, student_column)?;
+    let score_column = csv_file::column(&headers, score_column)?;
+
+    let mut rows = Vec::new();
+    for record in csv.records() {
+        let record = record.map_err(csv_file::unreadable)?;
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+        let student = record.get(student_column).unwrap_or_default().to_string();
+        let score = record.get(score_column).unwrap_or_default();
+
+        let (score, error) = match score.parse::<f64>() {
+            Ok(score) if score.is_finite() => (Some(score), None),
+            _ if score.is_empty() => (None, Some("No score".to_string())),
+            _ => (None, Some(format!("'{score}' is not a score"))),
+        };
+        rows.push(GradeImportRow {
+            line: record.position().map_or(0, |position| position.line()),
+            student,
+            student_id: None,
+            score,
+            status: match error {
+                Some(_) => GradeImportStatus::Invalid,
+                None => GradeImportStatus::Unmatched,
+            },
+            error,
+        });
+    }
+
+    Ok(rows)
+}
+
+/// Matches each `Unmatched` row to one of `students` by student ID, or else by
+/// email address ignoring case. A student matched by an earlier row makes the
+/// row `Invalid`.
+pub fn match_students(rows: &mut [GradeImportRow], students: &[Student]) {
+    let mut matched = HashSet::new();
+    for row in rows {
+        if row.status != GradeImportStatus::Unmatched {
+            continue;
+        }
+
+        let student = match row.student.parse::<i64>() {
+            Ok(id) => students.iter().find(|s| s.id == id),
+            Err(_) => students.iter().find(|s| {
+                s.email
+                    .as_deref()
+                    .is_some_and(|email| email.eq_ignore_ascii_case(&row.student))
+            }),
+        };
+        match student {
+            Some(student) if !matched.insert(student.id) => {
+                row.status = GradeImportStatus::Invalid;
+                row.error = Some("Student appears more than once".to_string());
+            }
+            Some(student) => {
+                row.student_id = Some(student.id);
+                row.status = GradeImportStatus::New;
+            }
+            None => {
+                row.error = Some(format!("No student '{}' in the class", row.student));
+            }
+        }
+    }
+}
+
+/// Whether an existing grade is one an import that keeps given grades may
+/// fill in: missing or incomplete work, or a grade without a score. Excused
+/// work is a grade given.
+pub fn is_empty_grade(score: Option<f64>, status: GradeStatus) -> bool {
+    match status {
+        GradeStatus::Graded => score.is_none(),
+        GradeStatus::Missing | GradeStatus::Incomplete => true,
+        GradeStatus::Excused => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn student(id: i64, email: Option<&str>) -> Student {
+        Student {
+            id,
+            first_name: "First".to_string(),
+            last_name: "Last".to_string(),
+            email: email.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn reads_scores_and_flags_unusable_ones() {
+        let csv =
+            "Email,Quiz Score\nada@example.com,9.5\ngrace@example.com,\nalan@example.com,ten\n";
+
+        let rows = parse_grade_rows(csv.as_bytes(), "email", "quiz score").unwrap();
+
+        assert_eq!(rows[0].score, Some(9.5));
+        assert_eq!(rows[0].status, GradeImportStatus::Unmatched);
+        assert_eq!(rows[1].error.as_deref(), Some("No score"));
+        assert_eq!(rows[2].error.as_deref(), Some("'ten' is not a score"));
+        assert_eq!(rows[2].line, 4);
+    }
+
+    #[test]
+    fn matches_by_id_or_email() {
+        let csv = "Student,Score\n7,8\nADA@example.com,9\nnobody@example.com,5\n1,4\n";
+        let mut rows = parse_grade_rows(csv.as_bytes(), "Student", "Score").unwrap();
+
+        match_students(
+            &mut rows,
+            &[student(1, Some("ada@example.com")), student(7, None)],
+        );
+
+        let matched: Vec<_> = rows.iter().map(|r| (r.status, r.student_id)).collect();
+        assert_eq!(
+            matched,
+            [
+                (GradeImportStatus::New, Some(7)),
+                (GradeImportStatus::New, Some(1)),
+                (GradeImportStatus::Unmatched, None),
+                (GradeImportStatus::Invalid, None),
+            ]
+        );
+        assert_eq!(
+            rows[2].error.as_deref(),
+            Some("No student 'nobody@example.com' in the class")
+        );
+    }
+
+    #[test]
+    fn excused_grades_are_not_empty() {
+        assert!(is_empty_grade(None, GradeStatus::Graded));
+        assert!(is_empty_grade(None, GradeStatus::Missing));
+        assert!(is_empty_grade(None, GradeStatus::Incomplete));
+        assert!(!is_empty_grade(Some(7.0), GradeStatus::Graded));
+        assert!(!is_empty_grade(None, GradeStatus::Excused));
+    }
+}
@@ -0,0 +1,268 @@
+use crate::{
+    csv_file::text_cell,
+    database::models::{
+        Assignment, Category, Grade, GradeStatus, GradebookExportOptions, OverallGrade, Student,
+    },
+};
+
+/// The rows of a class gradebook: a header, optionally the maximum points of
+/// each assignment, then one row per student. Assignments are ordered by due
+/// date, those without one last, and are followed by the percentage of each
+/// category and the overall percentage and letter grade. Scores are raw, as
+/// entered; curves only show in the percentages. Names are escaped so that
+/// spreadsheets never read them as formulas.
+pub fn gradebook_rows(
+    students: &[Student],
+    categories: &[Category],
+    assignments: &[Assignment],
+    grades: &[Grade],
+    overall_grades: &[OverallGrade],
+    options: GradebookExportOptions,
+) -> Vec<Vec<String>> {
+    let mut assignments: Vec<&Assignment> = assignments.iter().collect();
+    assignments.sort_by_key(|a| (a.due_date.is_none(), a.due_date, a.id));
+
+    let mut header = vec![
+        "Student ID".to_string(),
+        "Last Name".to_string(),
+        "First Name".to_string(),
+        "Email".to_string(),
+    ];
+    header.extend(assignments.iter().map(|a| text_cell(&a.assignment_name)));
+    header.extend(
+        categories
+            .iter()
+            .map(|c| text_cell(&format!("{} %", c.category_name))),
+    );
+    header.extend(["Overall %".to_string(), "Letter Grade".to_string()]);
+
+    let mut rows = vec![header];
+
+    if options.max_points_row {
+        let mut max_points = vec![
+            "Max Points".to_string(),
+            String::new(),
+            String::new(),
+            String::new(),
+        ];
+        max_points.extend(assignments.iter().map(|a| a.maximum_score.to_string()));
+        max_points.resize(rows[0].len(), String::new());
+        rows.push(max_points);
+    }
+
+    for student in students {
+        let mut row = vec![
+            student.id.to_string(),
+            text_cell(&student.last_name),
+            text_cell(&student.first_name),
+            text_cell(student.email.as_deref().unwrap_or_default()),
+        ];
+        row.extend(assignments.iter().map(|assignment| {
+            grades
+                .iter()
+                .find(|g| g.student_id == student.id && g.assignment_id == assignment.id)
+                .map(|grade| render_grade(grade, options))
+                .unwrap_or_default()
+        }));
+
+        let overall = overall_grades.iter().find(|o| o.student_id == student.id);
+        row.extend(categories.iter().map(|category| {
+            overall
+                .and_then(|o| {
+                    o.category_subtotals
+                        .iter()
+                        .find(|s| s.category_id == category.id)
+                })
+                .and_then(|subtotal| subtotal.percentage)
+                .map(format_percentage)
+                .unwrap_or_default()
+        }));
+        row.push(
+            overall
+                .map(|o| format_percentage(o.percentage))
+                .unwrap_or_default(),
+        );
+        row.push(
+            overall
+                .map(|o| text_cell(&o.letter_grade))
+                .unwrap_or_default(),
+        );
+
+        rows.push(row);
+    }
+
+    rows
+}
+
+fn render_grade(grade: &Grade, options: GradebookExportOptions) -> String {
+    let mark = match grade.status {
+        GradeStatus::Graded => {
+            return grade
+                .score
+                .map(|score| score.to_string())
+                .unwrap_or_default();
+        }
+        GradeStatus::Excused => "EX",
+        GradeStatus::Missing => "M",
+        GradeStatus::Incomplete => "I",
+    };
+    if options.status_marks {
+        mark.to_string()
+    } else {
+        String::new()
+    }
+}
+
+fn format_percentage(percentage: f64) -> String {
+    format!("{percentage:.2}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::{AssignmentKind, CategorySubtotal, GradingMode};
+    use chrono::NaiveDate;
+
+    fn student(id: i64, first_name: &str, last_name: &str) -> Student {
+        Student {
+            id,
+            first_name: first_name.to_string(),
+            last_name: last_name.to_string(),
+            email: None,
+        }
+    }
+
+    fn category(id: i64, category_name: &str) -> Category {
+        Category {
+            id,
+            class_id: 1,
+            category_name: category_name.to_string(),
+            weight: None,
+            drop_lowest: 0,
+            drop_highest: 0,
+        }
+    }
+
+    fn assignment(id: i64, assignment_name: &str, due_day: Option<u32>) -> Assignment {
+        Assignment {
+            id,
+            class_id: 1,
+            assignment_name: assignment_name.to_string(),
+            category_id: 1,
+            category_name: "Homework".to_string(),
+            maximum_score: 10.0,
+            due_date: due_day.map(|day| {
+                NaiveDate::from_ymd_opt(2025, 4, day)
+                    .unwrap()
+                    .and_hms_opt(9, 0, 0)
+                    .unwrap()
+            }),
+            allows_extra_credit: false,
+            assignment_kind: AssignmentKind::Regular,
+        }
+    }
+
+    fn grade(assignment_id: i64, score: Option<f64>, status: GradeStatus) -> Grade {
+        Grade {
+            student_id: 1,
+            assignment_id,
+            score,
+            status,
+            submitted_at: None,
+        }
+    }
+
+    fn overall_grade(percentage: f64) -> OverallGrade {
+        OverallGrade {
+            student_id: 1,
+            class_id: 1,
+            grading_mode: GradingMode::PercentAverage,
+            percentage,
+            letter_grade: "B".to_string(),
+            category_subtotals: vec![CategorySubtotal {
+                category_id: 1,
+                category_name: "Homework".to_string(),
+                weight: None,
+                graded_count: 2,
+                points_earned: 17.0,
+                points_possible: 20.0,
+                percentage: Some(percentage),
+            }],
+            dropped_grades: Vec::new(),
+        }
+    }
+
+    fn export(options: GradebookExportOptions) -> Vec<Vec<String>> {
+        gradebook_rows(
+            &[student(1, "Ada", "Lovelace"), student(2, "Alan", "Turing")],
+            &[category(1, "Homework")],
+            &[
+                assignment(1, "Essay", None),
+                assignment(2, "Quiz 2", Some(9)),
+                assignment(3, "Quiz 1", Some(2)),
+            ],
+            &[
+                grade(1, None, GradeStatus::Excused),
+                grade(2, Some(9.5), GradeStatus::Graded),
+                grade(3, None, GradeStatus::Missing),
+            ],
+            &[overall_grade(85.0)],
+            options,
+        )
+    }
+
+    #[test]
+    fn orders_assignments_by_due_date() {
+        let rows = export(GradebookExportOptions::default());
+
+        assert_eq!(
+            rows[0],
+            [
+                "Student ID",
+                "Last Name",
+                "First Name",
+                "Email",
+                "Quiz 1",
+                "Quiz 2",
+                "Essay",
+                "Homework %",
+                "Overall %",
+                "Letter Grade",
+            ]
+        );
+        assert_eq!(
+            rows[1],
+            ["1", "Lovelace", "Ada", "", "", "9.5", "", "85.00", "85.00", "B"]
+        );
+        assert_eq!(rows[2], ["2", "Turing", "Alan", "", "", "", "", "", "", ""]);
+    }
+
+    #[test]
+    fn marks_statuses_and_adds_max_points() {
+        let rows = export(GradebookExportOptions {
+            status_marks: true,
+            max_points_row: true,
+        });
+
+        assert_eq!(
+            rows[1],
+            ["Max Points", "", "", "", "10", "10", "10", "", "", ""]
+        );
+        assert_eq!(rows[2][4..7], ["M", "9.5", "EX"]);
+    }
+
+    #[test]
+    fn names_are_not_read_as_formulas() {
+        let rows = gradebook_rows(
+            &[student(1, "=HYPERLINK(\"x\")", "@Lovelace")],
+            &[category(1, "+Bonus")],
+            &[assignment(1, "-1 Quiz", None)],
+            &[],
+            &[],
+            GradebookExportOptions::default(),
+        );
+
+        assert_eq!(rows[0][4..6], ["'-1 Quiz", "'+Bonus %"]);
+        assert_eq!(rows[1][1..3], ["'@Lovelace", "'=HYPERLINK(\"x\")"]);
+    }
+}
@@ -0,0 +1,1389 @@
+use crate::database::models::{
+    Assignment, AssignmentCurve, AssignmentKind, Category, CategorySubtotal, CurveKind,
+    CurvedScore, DueDateOverride, Grade, GradeStatus, GradeThreshold, GradingMode, LatePolicy,
+    LateSubmission, OverallGrade, StudentAccommodation,
+};
+use chrono::{NaiveDateTime, TimeDelta};
+
+/// Everything about a class that decides how its grades are combined.
+#[derive(Debug)]
+pub struct GradingPolicy {
+    pub class_id: i64,
+    pub grading_mode: GradingMode,
+    pub categories: Vec<Category>,
+    /// Thresholds of the class's grading scale, in any order.
+    pub thresholds: Vec<GradeThreshold>,
+    /// The class-wide late policy and any per-assignment overrides.
+    pub late_policies: Vec<LatePolicy>,
+    /// Accommodations of the class's students, class-wide or not.
+    pub accommodations: Vec<StudentAccommodation>,
+    /// Due dates granted to single students for the class's assignments.
+    pub due_date_overrides: Vec<DueDateOverride>,
+    /// Curves applied to the class's assignments.
+    pub curves: Vec<AssignmentCurve>,
+}
+
+impl GradingPolicy {
+    /// The late policy of an assignment, falling back to the class policy.
+    fn late_policy_for(&self, assignment_id: i64) -> Option<&LatePolicy> {
+        self.late_policies
+            .iter()
+            .find(|p| p.assignment_id == Some(assignment_id))
+            .or_else(|| {
+                self.late_policies
+                    .iter()
+                    .find(|p| p.assignment_id.is_none())
+            })
+    }
+
+    /// Percentage taken off `grade` for being handed in late.
+    fn penalty_for(&self, assignment: &Assignment, grade: &Grade) -> f64 {
+        match (
+            self.late_policy_for(assignment.id),
+            effective_due_date(
+                assignment,
+                grade.student_id,
+                &self.due_date_overrides,
+                &self.accommodations,
+            ),
+            grade.submitted_at,
+        ) {
+            (Some(late_policy), Some(due_date), Some(submitted_at)) => {
+                late_penalty(late_policy, due_date, submitted_at)
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+struct ScoredGrade<'a> {
+    grade: &'a Grade,
+    score: f64,
+    maximum_score: f64,
+    extra_credit: bool,
+}
+
+impl ScoredGrade<'_> {
+    /// `None` for assignments without a positive maximum, which have no
+    /// meaningful percentage.
+    fn percentage(&self) -> Option<f64> {
+        (self.maximum_score > 0.0).then(|| self.score / self.maximum_score * 100.0)
+    }
+}
+
+/// Calculates the overall grade of every student in `student_ids` from the
+/// class's assignments and grades, with curves applied. Students without a
+/// single counted grade are left out, as are grades for assignments of other
+/// classes.
+pub fn calculate_overall_grades(
+    policy: &GradingPolicy,
+    student_ids: &[i64],
+    assignments: &[Assignment],
+    grades: &[Grade],
+) -> Vec<OverallGrade> {
+    let grades = curved_grades(policy, assignments, grades);
+    student_ids
+        .iter()
+        .filter_map(|&student_id| calculate_student_grade(policy, student_id, assignments, &grades))
+        .collect()
+}
+
+fn calculate_student_grade(
+    policy: &GradingPolicy,
+    student_id: i64,
+    assignments: &[Assignment],
+    grades: &[Grade],
+) -> Option<OverallGrade> {
+    let mut category_subtotals = Vec::new();
+    let mut dropped_grades = Vec::new();
+    let mut counted_percentages = Vec::new();
+    let mut bonus_percentages = Vec::new();
+    let mut bonus_categories = Vec::new();
+    let mut unweighted_bonus = Vec::new();
+
+    for category in &policy.categories {
+        let scored: Vec<ScoredGrade> = grades
+            .iter()
+            .filter(|g| g.student_id == student_id)
+            .filter_map(|grade| {
+                let assignment = assignments.iter().find(|a| {
+                    a.id == grade.assignment_id
+                        && a.class_id == policy.class_id
+                        && a.category_id == category.id
+                })?;
+                Some(ScoredGrade {
+                    grade,
+                    score: adjusted_score(policy, assignment, grade)?,
+                    maximum_score: assignment.maximum_score,
+                    extra_credit: assignment.assignment_kind == AssignmentKind::ExtraCredit,
+                })
+            })
+            .collect();
+
+        if scored.is_empty() {
+            continue;
+        }
+
+        let dropped = dropped_indices(&scored, category);
+        let mut counted = Vec::with_capacity(scored.len());
+        for (index, scored_grade) in scored.into_iter().enumerate() {
+            if dropped.contains(&index) {
+                dropped_grades.push(scored_grade.grade.clone());
+            } else {
+                counted.push(scored_grade);
+            }
+        }
+
+        let (bonus, regular): (Vec<&ScoredGrade>, Vec<&ScoredGrade>) =
+            counted.iter().partition(|s| s.extra_credit);
+        let percentages: Vec<f64> = regular.iter().filter_map(|s| s.percentage()).collect();
+        let bonus: Vec<f64> = bonus.iter().filter_map(|s| s.percentage()).collect();
+        counted_percentages.extend_from_slice(&percentages);
+        bonus_percentages.extend_from_slice(&bonus);
+
+        // A category of nothing but extra credit, such as "Bonus", is scored
+        // on its own work so its weight can be added on top.
+        let percentage = if percentages.is_empty() && !bonus.is_empty() {
+            bonus_categories.push(category.id);
+            if category.weight.is_none() {
+                unweighted_bonus.extend_from_slice(&bonus);
+            }
+            Some(bonus.iter().sum::<f64>() / bonus.len() as f64)
+        } else {
+            average_with_bonus(&percentages, &bonus)
+        };
+
+        category_subtotals.push(CategorySubtotal {
+            category_id: category.id,
+            category_name: category.category_name.clone(),
+            weight: category.weight,
+            graded_count: counted.len(),
+            points_earned: counted.iter().map(|s| s.score).sum(),
+            points_possible: regular.iter().map(|s| s.maximum_score).sum(),
+            percentage,
+        });
+    }
+
+    let percentage = match policy.grading_mode {
+        GradingMode::PercentAverage => average_with_bonus(&counted_percentages, &bonus_percentages),
+        GradingMode::TotalPoints => total_points_percentage(&category_subtotals),
+        // Extra credit categories without a weight are added on top as in
+        // the percent average.
+        GradingMode::WeightedCategories => {
+            weighted_percentage(&category_subtotals, &bonus_categories)
+                .map(|weighted| {
+                    weighted
+                        + unweighted_bonus.iter().sum::<f64>() / counted_percentages.len() as f64
+                })
+                .or_else(|| average_with_bonus(&counted_percentages, &bonus_percentages))
+        }
+    }?;
+
+    Some(OverallGrade {
+        student_id,
+        class_id: policy.class_id,
+        grading_mode: policy.grading_mode,
+        percentage,
+        letter_grade: letter_grade(percentage, &policy.thresholds),
+        category_subtotals,
+        dropped_grades,
+    })
+}
+
+/// Moves `scores`, all graded scores of `assignment`, along a curve. The curve
+/// is fitted to the scores as a whole. Curved scores never drop below zero, nor
+/// above the maximum unless the assignment allows extra credit.
+pub fn curve_scores(
+    curve_kind: CurveKind,
+    curve_value: Option<f64>,
+    assignment: &Assignment,
+    scores: &[f64],
+) -> Vec<f64> {
+    let maximum_score = assignment.maximum_score;
+    let highest = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let mean = scores.iter().sum::<f64>() / scores.len() as f64;
+    let value = curve_value.unwrap_or(0.0);
+    let ceiling = if assignment.allows_extra_credit {
+        f64::INFINITY
+    } else {
+        maximum_score
+    };
+
+    scores
+        .iter()
+        .map(|&score| {
+            let curved = match curve_kind {
+                CurveKind::FlatAdd => score + value,
+                CurveKind::ScaleToHighest if highest > 0.0 => score / highest * maximum_score,
+                CurveKind::SquareRoot if maximum_score > 0.0 => {
+                    (score.max(0.0) / maximum_score).sqrt() * maximum_score
+                }
+                CurveKind::TargetMean => score + value / 100.0 * maximum_score - mean,
+                _ => score,
+            };
+            curved.min(ceiling).max(0.0)
+        })
+        .collect()
+}
+
+/// The before and after of curving `assignment`, one row per grade in
+/// `grades`. Only graded scores are curved.
+pub fn preview_curve(
+    curve_kind: CurveKind,
+    curve_value: Option<f64>,
+    assignment: &Assignment,
+    grades: &[Grade],
+) -> Vec<CurvedScore> {
+    let grades: Vec<&Grade> = grades
+        .iter()
+        .filter(|g| g.assignment_id == assignment.id)
+        .collect();
+    let raw: Vec<f64> = grades.iter().filter_map(|g| curvable_score(g)).collect();
+    let mut curved = curve_scores(curve_kind, curve_value, assignment, &raw).into_iter();
+
+    grades
+        .into_iter()
+        .map(|grade| CurvedScore {
+            student_id: grade.student_id,
+            status: grade.status,
+            raw_score: grade.score,
+            curved_score: match curvable_score(grade) {
+                Some(_) => curved.next(),
+                None => grade.score,
+            },
+        })
+        .collect()
+}
+
+/// `grades` with the graded scores of every curved assignment replaced by
+/// their curved scores.
+fn curved_grades(
+    policy: &GradingPolicy,
+    assignments: &[Assignment],
+    grades: &[Grade],
+) -> Vec<Grade> {
+    let mut curved = grades.to_vec();
+    for curve in &policy.curves {
+        let Some(assignment) = assignments
+            .iter()
+            .find(|a| a.id == curve.assignment_id && a.class_id == policy.class_id)
+        else {
+            continue;
+        };
+
+        let (indices, raw): (Vec<usize>, Vec<f64>) = curved
+            .iter()
+            .enumerate()
+            .filter(|(_, g)| g.assignment_id == assignment.id)
+            .filter_map(|(i, g)| Some((i, curvable_score(g)?)))
+            .unzip();
+        let scores = curve_scores(curve.curve_kind, curve.curve_value, assignment, &raw);
+        for (i, score) in indices.into_iter().zip(scores) {
+            curved[i].score = Some(score);
+        }
+    }
+    curved
+}
+
+/// The score a curve moves, which only graded scores have.
+fn curvable_score(grade: &Grade) -> Option<f64> {
+    match grade.status {
+        GradeStatus::Graded => grade.score,
+        _ => None,
+    }
+}
+
+/// Percentage each of `student_ids` scored on `assignment` as it counts
+/// towards their overall grade, with the curve applied and late penalties
+/// taken off. Grades that do not count, or belong to students no longer in the
+/// class, are left out.
+pub fn assignment_percentages(
+    policy: &GradingPolicy,
+    student_ids: &[i64],
+    assignment: &Assignment,
+    grades: &[Grade],
+) -> Vec<(i64, f64)> {
+    if assignment.maximum_score <= 0.0 {
+        return Vec::new();
+    }
+
+    curved_grades(policy, std::slice::from_ref(assignment), grades)
+        .iter()
+        .filter(|g| g.assignment_id == assignment.id && student_ids.contains(&g.student_id))
+        .filter_map(|grade| {
+            let score = adjusted_score(policy, assignment, grade)?;
+            Some((grade.student_id, score / assignment.maximum_score * 100.0))
+        })
+        .collect()
+}
+
+/// The score `grade` contributes after its late penalty, or `None` when it
+/// does not count at all.
+fn adjusted_score(policy: &GradingPolicy, assignment: &Assignment, grade: &Grade) -> Option<f64> {
+    let penalty = policy.penalty_for(assignment, grade);
+    Some(counted_score(grade)? * (1.0 - penalty / 100.0))
+}
+
+/// The score a grade contributes, or `None` when it does not count at all.
+fn counted_score(grade: &Grade) -> Option<f64> {
+    match grade.status {
+        GradeStatus::Graded => grade.score,
+        GradeStatus::Missing => Some(0.0),
+        GradeStatus::Excused | GradeStatus::Incomplete => None,
+    }
+}
+
+/// The due date `student_id` is held to for `assignment`. An override for the
+/// student wins outright; otherwise the assignment's due date is pushed back
+/// by the student's accommodation, where one for the assignment's class wins
+/// over one that applies to every class. The accommodation's time multiplier
+/// plays no part. An extension that runs past the calendar leaves the student
+/// without a due date.
+pub fn effective_due_date(
+    assignment: &Assignment,
+    student_id: i64,
+    overrides: &[DueDateOverride],
+    accommodations: &[StudentAccommodation],
+) -> Option<NaiveDateTime> {
+    if let Some(due_date_override) = overrides
+        .iter()
+        .find(|o| o.student_id == student_id && o.assignment_id == assignment.id)
+    {
+        return Some(due_date_override.due_date);
+    }
+
+    let due_date = assignment.due_date?;
+    let accommodation = accommodations
+        .iter()
+        .filter(|a| a.student_id == student_id)
+        .find(|a| a.class_id == Some(assignment.class_id))
+        .or_else(|| {
+            accommodations
+                .iter()
+                .find(|a| a.student_id == student_id && a.class_id.is_none())
+        });
+
+    match accommodation {
+        Some(accommodation) => TimeDelta::try_days(accommodation.extension_days)
+            .and_then(|extension| due_date.checked_add_signed(extension)),
+        None => Some(due_date),
+    }
+}
+
+/// Whole days, rounded up, that `submitted_at` lies past `due_date`.
+pub fn days_late(due_date: NaiveDateTime, submitted_at: NaiveDateTime) -> i64 {
+    let seconds_late = (submitted_at - due_date).num_seconds();
+    if seconds_late <= 0 {
+        0
+    } else {
+        (seconds_late + 86_399) / 86_400
+    }
+}
+
+/// Percentage of the score `late_policy` takes off work handed in at
+/// `submitted_at`. Nothing is taken off within the grace period; after it the
+/// days late are still counted from the due date.
+pub fn late_penalty(
+    late_policy: &LatePolicy,
+    due_date: NaiveDateTime,
+    submitted_at: NaiveDateTime,
+) -> f64 {
+    // A grace period that runs past the calendar never ends.
+    let grace_period_ends =
+        TimeDelta::try_milliseconds((late_policy.grace_period_hours * 3_600_000.0) as i64)
+            .and_then(|grace_period| due_date.checked_add_signed(grace_period));
+    if grace_period_ends.is_none_or(|ends| submitted_at <= ends) {
+        return 0.0;
+    }
+
+    let days_late = days_late(due_date, submitted_at);
+    if late_policy
+        .zero_after_days
+        .is_some_and(|zero_after_days| days_late > zero_after_days)
+    {
+        return 100.0;
+    }
+
+    let max_penalty = late_policy.max_penalty.unwrap_or(100.0).min(100.0);
+    (days_late as f64 * late_policy.penalty_per_day).min(max_penalty)
+}
+
+/// Every grade of the class handed in after the student's effective due date,
+/// with the penalty its late policy applies. Raw scores are reported untouched alongside.
+pub fn late_submissions(
+    policy: &GradingPolicy,
+    assignments: &[Assignment],
+    grades: &[Grade],
+) -> Vec<LateSubmission> {
+    grades
+        .iter()
+        .filter_map(|grade| {
+            let assignment = assignments
+                .iter()
+                .find(|a| a.id == grade.assignment_id && a.class_id == policy.class_id)?;
+            let due_date = effective_due_date(
+                assignment,
+                grade.student_id,
+                &policy.due_date_overrides,
+                &policy.accommodations,
+            )?;
+            let submitted_at = grade.submitted_at?;
+            if submitted_at <= due_date {
+                return None;
+            }
+
+            let penalty_percentage = policy.penalty_for(assignment, grade);
+            Some(LateSubmission {
+                student_id: grade.student_id,
+                assignment_id: grade.assignment_id,
+                due_date,
+                submitted_at,
+                days_late: days_late(due_date, submitted_at),
+                penalty_percentage,
+                raw_score: grade.score,
+                adjusted_score: grade
+                    .score
+                    .map(|score| score * (1.0 - penalty_percentage / 100.0)),
+            })
+        })
+        .collect()
+}
+
+/// Indices into `scored` removed by the category's drop rules. Only regular
+/// grades with a percentage can be dropped, and nothing is dropped unless at
+/// least one of them would remain.
+fn dropped_indices(scored: &[ScoredGrade], category: &Category) -> Vec<usize> {
+    let drop_lowest = category.drop_lowest.max(0) as usize;
+    let drop_highest = category.drop_highest.max(0) as usize;
+
+    let mut ranked: Vec<(usize, f64)> = scored
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| !s.extra_credit)
+        .filter_map(|(i, s)| s.percentage().map(|p| (i, p)))
+        .collect();
+
+    if ranked.len() <= drop_lowest + drop_highest {
+        return Vec::new();
+    }
+
+    ranked.sort_by(|(ia, pa), (ib, pb)| {
+        pa.total_cmp(pb).then_with(|| {
+            scored[*ia]
+                .grade
+                .assignment_id
+                .cmp(&scored[*ib].grade.assignment_id)
+        })
+    });
+
+    let lowest = ranked.iter().take(drop_lowest);
+    let highest = ranked.iter().rev().take(drop_highest);
+    lowest.chain(highest).map(|(i, _)| *i).collect()
+}
+
+/// Mean of `percentages` with the extra credit `bonus` percentages added on
+/// top, so that bonus work never counts towards the denominator.
+fn average_with_bonus(percentages: &[f64], bonus: &[f64]) -> Option<f64> {
+    let total: f64 = percentages.iter().chain(bonus).sum();
+    (!percentages.is_empty()).then(|| total / percentages.len() as f64)
+}
+
+fn total_points_percentage(subtotals: &[CategorySubtotal]) -> Option<f64> {
+    let earned: f64 = subtotals.iter().map(|s| s.points_earned).sum();
+    let possible: f64 = subtotals.iter().map(|s| s.points_possible).sum();
+    (possible > 0.0).then(|| earned / possible * 100.0)
+}
+
+/// Weighted average of the category percentages, renormalised over the
+/// categories that have both a weight and a percentage. The extra credit
+/// categories in `bonus_categories` add their weighted percentage on top
+/// without counting towards the total weight.
+fn weighted_percentage(subtotals: &[CategorySubtotal], bonus_categories: &[i64]) -> Option<f64> {
+    let (weighted_sum, total_weight) = subtotals
+        .iter()
+        .filter_map(|s| Some((s.category_id, s.percentage?, s.weight?)))
+        .fold(
+            (0.0, 0.0),
+            |(sum, total), (category_id, percentage, weight)| {
+                if bonus_categories.contains(&category_id) {
+                    (sum + percentage * weight, total)
+                } else {
+                    (sum + percentage * weight, total + weight)
+                }
+            },
+        );
+    (total_weight > 0.0).then(|| weighted_sum / total_weight)
+}
+
+/// The letter of the highest threshold `percentage` reaches, falling back to
+/// the lowest threshold when it reaches none.
+pub fn letter_grade(percentage: f64, thresholds: &[GradeThreshold]) -> String {
+    thresholds
+        .iter()
+        .filter(|t| percentage >= t.min_percentage)
+        .max_by(|a, b| a.min_percentage.total_cmp(&b.min_percentage))
+        .or_else(|| {
+            thresholds
+                .iter()
+                .min_by(|a, b| a.min_percentage.total_cmp(&b.min_percentage))
+        })
+        .map(|t| t.letter_grade.clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLASS_ID: i64 = 1;
+
+    fn category(id: i64, weight: Option<f64>) -> Category {
+        Category {
+            id,
+            class_id: CLASS_ID,
+            category_name: format!("Category {id}"),
+            weight,
+            drop_lowest: 0,
+            drop_highest: 0,
+        }
+    }
+
+    fn assignment(id: i64, category_id: i64, maximum_score: f64) -> Assignment {
+        Assignment {
+            id,
+            class_id: CLASS_ID,
+            assignment_name: format!("Assignment {id}"),
+            category_id,
+            category_name: format!("Category {category_id}"),
+            maximum_score,
+            due_date: None,
+            allows_extra_credit: false,
+            assignment_kind: AssignmentKind::Regular,
+        }
+    }
+
+    fn extra_credit(id: i64, category_id: i64, maximum_score: f64) -> Assignment {
+        Assignment {
+            assignment_kind: AssignmentKind::ExtraCredit,
+            ..assignment(id, category_id, maximum_score)
+        }
+    }
+
+    fn grade(student_id: i64, assignment_id: i64, score: f64) -> Grade {
+        Grade {
+            student_id,
+            assignment_id,
+            score: Some(score),
+            status: GradeStatus::Graded,
+            submitted_at: None,
+        }
+    }
+
+    fn ungraded(student_id: i64, assignment_id: i64, status: GradeStatus) -> Grade {
+        Grade {
+            student_id,
+            assignment_id,
+            score: None,
+            status,
+            submitted_at: None,
+        }
+    }
+
+    fn date(day: u32, hour: u32) -> NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2025, 3, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn late_policy(assignment_id: Option<i64>, penalty_per_day: f64) -> LatePolicy {
+        LatePolicy {
+            id: 0,
+            class_id: CLASS_ID,
+            assignment_id,
+            penalty_per_day,
+            max_penalty: None,
+            grace_period_hours: 0.0,
+            zero_after_days: None,
+        }
+    }
+
+    fn standard_scale() -> Vec<GradeThreshold> {
+        [
+            ("A", 90.0),
+            ("B", 80.0),
+            ("C", 70.0),
+            ("D", 60.0),
+            ("F", 0.0),
+        ]
+        .into_iter()
+        .map(|(letter_grade, min_percentage)| GradeThreshold {
+            letter_grade: letter_grade.to_string(),
+            min_percentage,
+        })
+        .collect()
+    }
+
+    fn policy(grading_mode: GradingMode, categories: Vec<Category>) -> GradingPolicy {
+        GradingPolicy {
+            class_id: CLASS_ID,
+            grading_mode,
+            categories,
+            thresholds: standard_scale(),
+            late_policies: Vec::new(),
+            accommodations: Vec::new(),
+            due_date_overrides: Vec::new(),
+            curves: Vec::new(),
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn students_without_grades_are_left_out() {
+        let policy = policy(GradingMode::WeightedCategories, vec![category(1, None)]);
+        let assignments = vec![assignment(1, 1, 10.0)];
+        let grades = vec![grade(1, 1, 10.0)];
+
+        let result = calculate_overall_grades(&policy, &[1, 2], &assignments, &grades);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].student_id, 1);
+    }
+
+    #[test]
+    fn class_without_assignments_has_no_grades() {
+        let policy = policy(GradingMode::PercentAverage, vec![category(1, Some(100.0))]);
+
+        let result = calculate_overall_grades(&policy, &[1, 2, 3], &[], &[]);
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn percent_average_treats_assignments_equally() {
+        let policy = policy(GradingMode::PercentAverage, vec![category(1, None)]);
+        let assignments = vec![assignment(1, 1, 5.0), assignment(2, 1, 100.0)];
+        let grades = vec![grade(1, 1, 5.0), grade(1, 2, 50.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_close(result[0].percentage, 75.0);
+        assert_eq!(result[0].letter_grade, "C");
+    }
+
+    #[test]
+    fn total_points_divides_earned_by_possible() {
+        let policy = policy(GradingMode::TotalPoints, vec![category(1, None)]);
+        let assignments = vec![assignment(1, 1, 5.0), assignment(2, 1, 100.0)];
+        let grades = vec![grade(1, 1, 5.0), grade(1, 2, 50.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_close(result[0].percentage, 55.0 / 105.0 * 100.0);
+    }
+
+    #[test]
+    fn weighted_categories_combine_category_averages() {
+        let policy = policy(
+            GradingMode::WeightedCategories,
+            vec![category(1, Some(30.0)), category(2, Some(70.0))],
+        );
+        let assignments = vec![assignment(1, 1, 5.0), assignment(2, 2, 100.0)];
+        let grades = vec![grade(1, 1, 5.0), grade(1, 2, 50.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_close(result[0].percentage, 65.0);
+        assert_eq!(result[0].letter_grade, "D");
+        assert_eq!(result[0].category_subtotals.len(), 2);
+        assert_close(result[0].category_subtotals[0].percentage.unwrap(), 100.0);
+        assert_close(result[0].category_subtotals[1].percentage.unwrap(), 50.0);
+    }
+
+    #[test]
+    fn weights_are_renormalised_over_partial_categories() {
+        let policy = policy(
+            GradingMode::WeightedCategories,
+            vec![
+                category(1, Some(20.0)),
+                category(2, Some(30.0)),
+                category(3, Some(50.0)),
+            ],
+        );
+        let assignments = vec![
+            assignment(1, 1, 10.0),
+            assignment(2, 2, 10.0),
+            assignment(3, 3, 10.0),
+        ];
+        let grades = vec![grade(1, 1, 10.0), grade(1, 2, 5.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_close(result[0].percentage, (100.0 * 20.0 + 50.0 * 30.0) / 50.0);
+        assert_eq!(result[0].category_subtotals.len(), 2);
+    }
+
+    #[test]
+    fn unweighted_categories_fall_back_to_the_flat_average() {
+        let policy = policy(
+            GradingMode::WeightedCategories,
+            vec![category(1, None), category(2, Some(0.0))],
+        );
+        let assignments = vec![assignment(1, 1, 10.0), assignment(2, 2, 10.0)];
+        let grades = vec![grade(1, 1, 10.0), grade(1, 2, 0.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_close(result[0].percentage, 50.0);
+    }
+
+    #[test]
+    fn categories_without_weight_are_ignored_when_others_have_one() {
+        let policy = policy(
+            GradingMode::WeightedCategories,
+            vec![category(1, Some(100.0)), category(2, None)],
+        );
+        let assignments = vec![assignment(1, 1, 10.0), assignment(2, 2, 10.0)];
+        let grades = vec![grade(1, 1, 8.0), grade(1, 2, 0.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_close(result[0].percentage, 80.0);
+    }
+
+    #[test]
+    fn zero_maximum_assignments_do_not_divide_by_zero() {
+        let policy = policy(GradingMode::PercentAverage, vec![category(1, None)]);
+        let assignments = vec![assignment(1, 1, 0.0), assignment(2, 1, 10.0)];
+        let grades = vec![grade(1, 1, 0.0), grade(1, 2, 9.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_close(result[0].percentage, 90.0);
+        assert!(result[0].percentage.is_finite());
+    }
+
+    #[test]
+    fn only_zero_maximum_assignments_yield_no_grade() {
+        let policy = policy(GradingMode::TotalPoints, vec![category(1, None)]);
+        let assignments = vec![assignment(1, 1, 0.0)];
+        let grades = vec![grade(1, 1, 0.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn drop_lowest_removes_the_worst_percentages() {
+        let mut homework = category(1, None);
+        homework.drop_lowest = 1;
+        let policy = policy(GradingMode::PercentAverage, vec![homework]);
+        let assignments = vec![
+            assignment(1, 1, 10.0),
+            assignment(2, 1, 100.0),
+            assignment(3, 1, 10.0),
+        ];
+        let grades = vec![grade(1, 1, 1.0), grade(1, 2, 50.0), grade(1, 3, 9.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_close(result[0].percentage, 70.0);
+        assert_eq!(result[0].dropped_grades.len(), 1);
+        assert_eq!(result[0].dropped_grades[0].assignment_id, 1);
+        assert_eq!(result[0].category_subtotals[0].graded_count, 2);
+    }
+
+    #[test]
+    fn drop_highest_removes_the_best_percentages() {
+        let mut quizzes = category(1, None);
+        quizzes.drop_highest = 1;
+        let policy = policy(GradingMode::PercentAverage, vec![quizzes]);
+        let assignments = vec![assignment(1, 1, 10.0), assignment(2, 1, 10.0)];
+        let grades = vec![grade(1, 1, 10.0), grade(1, 2, 6.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_close(result[0].percentage, 60.0);
+        assert_eq!(result[0].dropped_grades[0].assignment_id, 1);
+    }
+
+    #[test]
+    fn drops_never_remove_every_grade() {
+        let mut homework = category(1, None);
+        homework.drop_lowest = 1;
+        homework.drop_highest = 1;
+        let policy = policy(GradingMode::PercentAverage, vec![homework]);
+        let assignments = vec![assignment(1, 1, 10.0), assignment(2, 1, 10.0)];
+        let grades = vec![grade(1, 1, 4.0), grade(1, 2, 8.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_close(result[0].percentage, 60.0);
+        assert!(result[0].dropped_grades.is_empty());
+    }
+
+    #[test]
+    fn drop_ties_are_broken_by_assignment_id() {
+        let mut homework = category(1, None);
+        homework.drop_lowest = 1;
+        let policy = policy(GradingMode::PercentAverage, vec![homework]);
+        let assignments = vec![
+            assignment(3, 1, 10.0),
+            assignment(1, 1, 10.0),
+            assignment(2, 1, 10.0),
+        ];
+        let grades = vec![grade(1, 3, 5.0), grade(1, 1, 5.0), grade(1, 2, 10.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_eq!(result[0].dropped_grades[0].assignment_id, 1);
+    }
+
+    #[test]
+    fn other_students_and_classes_are_ignored() {
+        let policy = policy(GradingMode::PercentAverage, vec![category(1, None)]);
+        let mut foreign = assignment(2, 1, 10.0);
+        foreign.class_id = CLASS_ID + 1;
+        let assignments = vec![assignment(1, 1, 10.0), foreign];
+        let grades = vec![grade(1, 1, 10.0), grade(1, 2, 0.0), grade(2, 1, 0.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_eq!(result.len(), 1);
+        assert_close(result[0].percentage, 100.0);
+    }
+
+    #[test]
+    fn excused_work_does_not_count() {
+        let policy = policy(GradingMode::TotalPoints, vec![category(1, None)]);
+        let assignments = vec![assignment(1, 1, 10.0), assignment(2, 1, 10.0)];
+        let grades = vec![grade(1, 1, 8.0), ungraded(1, 2, GradeStatus::Excused)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_close(result[0].percentage, 80.0);
+        assert_eq!(result[0].category_subtotals[0].graded_count, 1);
+    }
+
+    #[test]
+    fn missing_work_counts_as_zero() {
+        let policy = policy(GradingMode::PercentAverage, vec![category(1, None)]);
+        let assignments = vec![assignment(1, 1, 10.0), assignment(2, 1, 10.0)];
+        let mut missing = ungraded(1, 2, GradeStatus::Missing);
+        missing.score = Some(10.0);
+        let grades = vec![grade(1, 1, 8.0), missing];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_close(result[0].percentage, 40.0);
+    }
+
+    #[test]
+    fn incomplete_work_is_left_out_until_graded() {
+        let policy = policy(GradingMode::PercentAverage, vec![category(1, None)]);
+        let assignments = vec![assignment(1, 1, 10.0)];
+        let grades = vec![ungraded(1, 1, GradeStatus::Incomplete)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn missing_work_can_be_dropped() {
+        let mut homework = category(1, None);
+        homework.drop_lowest = 1;
+        let policy = policy(GradingMode::PercentAverage, vec![homework]);
+        let assignments = vec![assignment(1, 1, 10.0), assignment(2, 1, 10.0)];
+        let grades = vec![grade(1, 1, 9.0), ungraded(1, 2, GradeStatus::Missing)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_close(result[0].percentage, 90.0);
+        assert_eq!(result[0].dropped_grades[0].assignment_id, 2);
+    }
+
+    #[test]
+    fn scores_above_the_maximum_raise_the_percentage() {
+        let policy = policy(GradingMode::PercentAverage, vec![category(1, None)]);
+        let mut bonus_question = assignment(1, 1, 10.0);
+        bonus_question.allows_extra_credit = true;
+        let assignments = vec![bonus_question, assignment(2, 1, 10.0)];
+        let grades = vec![grade(1, 1, 12.0), grade(1, 2, 8.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_close(result[0].percentage, 100.0);
+    }
+
+    #[test]
+    fn extra_credit_adds_points_without_adding_to_the_denominator() {
+        let policy = policy(GradingMode::TotalPoints, vec![category(1, None)]);
+        let assignments = vec![assignment(1, 1, 100.0), extra_credit(2, 1, 10.0)];
+        let grades = vec![grade(1, 1, 80.0), grade(1, 2, 5.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_close(result[0].percentage, 85.0);
+        assert_close(result[0].category_subtotals[0].points_earned, 85.0);
+        assert_close(result[0].category_subtotals[0].points_possible, 100.0);
+    }
+
+    #[test]
+    fn extra_credit_is_added_on_top_of_the_average() {
+        let policy = policy(GradingMode::PercentAverage, vec![category(1, None)]);
+        let assignments = vec![
+            assignment(1, 1, 10.0),
+            assignment(2, 1, 10.0),
+            extra_credit(3, 1, 10.0),
+        ];
+        let grades = vec![grade(1, 1, 8.0), grade(1, 2, 6.0), grade(1, 3, 2.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_close(result[0].percentage, 80.0);
+    }
+
+    #[test]
+    fn extra_credit_only_categories_add_their_weight_on_top() {
+        let policy = policy(
+            GradingMode::WeightedCategories,
+            vec![category(1, Some(80.0)), category(2, Some(10.0))],
+        );
+        let assignments = vec![
+            assignment(1, 1, 10.0),
+            extra_credit(2, 2, 10.0),
+            extra_credit(3, 2, 10.0),
+        ];
+        let grades = vec![
+            grade(1, 1, 7.0),
+            grade(1, 2, 10.0),
+            grade(1, 3, 6.0),
+            grade(2, 1, 7.0),
+        ];
+
+        let result = calculate_overall_grades(&policy, &[1, 2], &assignments, &grades);
+
+        assert_close(result[0].percentage, 80.0);
+        assert_eq!(result[0].category_subtotals[1].percentage, Some(80.0));
+        assert_close(result[1].percentage, 70.0);
+    }
+
+    #[test]
+    fn unweighted_extra_credit_categories_are_added_as_in_the_average() {
+        let policy = policy(
+            GradingMode::WeightedCategories,
+            vec![category(1, Some(80.0)), category(2, None)],
+        );
+        let assignments = vec![
+            assignment(1, 1, 10.0),
+            assignment(2, 1, 10.0),
+            extra_credit(3, 2, 10.0),
+        ];
+        let grades = vec![grade(1, 1, 7.0), grade(1, 2, 7.0), grade(1, 3, 2.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_close(result[0].percentage, 80.0);
+    }
+
+    #[test]
+    fn extra_credit_is_never_dropped() {
+        let mut homework = category(1, None);
+        homework.drop_lowest = 1;
+        let policy = policy(GradingMode::PercentAverage, vec![homework]);
+        let assignments = vec![
+            assignment(1, 1, 10.0),
+            assignment(2, 1, 10.0),
+            extra_credit(3, 1, 10.0),
+        ];
+        let grades = vec![grade(1, 1, 5.0), grade(1, 2, 9.0), grade(1, 3, 0.0)];
+
+        let result = calculate_overall_grades(&policy, &[1], &assignments, &grades);
+
+        assert_eq!(result[0].dropped_grades[0].assignment_id, 1);
+        assert_close(result[0].percentage, 90.0);
+    }
+
+    #[test]
+    fn days_late_round_up_to_whole_days() {
+        assert_eq!(days_late(date(10, 12), date(10, 12)), 0);
+        assert_eq!(days_late(date(10, 12), date(9, 12)), 0);
+        assert_eq!(days_late(date(10, 12), date(10, 13)), 1);
+        assert_eq!(days_late(date(10, 12), date(11, 12)), 1);
+        assert_eq!(days_late(date(10, 12), date(11, 13)), 2);
+    }
+
+    #[test]
+    fn late_penalty_accumulates_per_day_up_to_the_cap() {
+        let mut late_policy = late_policy(None, 10.0);
+        late_policy.max_penalty = Some(25.0);
+
+        assert_close(late_penalty(&late_policy, date(10, 12), date(10, 12)), 0.0);
+        assert_close(late_penalty(&late_policy, date(10, 12), date(11, 12)), 10.0);
+        assert_close(late_penalty(&late_policy, date(10, 12), date(12, 12)), 20.0);
+        assert_close(late_penalty(&late_policy, date(10, 12), date(20, 12)), 25.0);
+    }
+
+    #[test]
+    fn late_penalty_respects_the_grace_period() {
+        let mut late_policy = late_policy(None, 10.0);
+        late_policy.grace_period_hours = 6.0;
+
+        assert_close(late_penalty(&late_policy, date(10, 12), date(10, 18)), 0.0);
+        assert_close(late_penalty(&late_policy, date(10, 12), date(10, 19)), 10.0);
+    }
+
+    #[test]
+    fn endless_grace_periods_do_not_overflow() {
+        let mut late_policy = late_policy(None, 10.0);
+        late_policy.grace_period_hours = 1e10;
+
+        assert_close(late_penalty(&late_policy, date(10, 12), date(20, 12)), 0.0);
+    }
+
+    #[test]
+    fn late_penalty_zeroes_work_past_the_cutoff() {
+        let mut late_policy = late_policy(None, 5.0);
+        late_policy.zero_after_days = Some(2);
+
+        assert_close(late_penalty(&late_policy, date(10, 12), date(12, 12)), 10.0);
+        assert_close(
+            late_penalty(&late_policy, date(10, 12), date(12, 13)),
+            100.0,
+        );
+    }
+
+    #[test]
+    fn late_work_is_penalised_in_the_overall_grade() {
+        let mut policy = policy(GradingMode::PercentAverage, vec![category(1, None)]);
+        policy.late_policies = vec![late_policy(None, 10.0), late_policy(Some(2), 50.0)];
+        let mut first = assignment(1, 1, 10.0);
+        first.due_date = Some(date(10, 12));
+        let mut second = assignment(2, 1, 10.0);
+        second.due_date = Some(date(10, 12));
+        let mut first_grade = grade(1, 1, 10.0);
+        first_grade.submitted_at = Some(date(12, 12));
+        let mut second_grade = grade(1, 2, 10.0);
+        second_grade.submitted_at = Some(date(11, 12));
+
+        let result = calculate_overall_grades(
+            &policy,
+            &[1],
+            &[first, second],
+            &[first_grade, second_grade],
+        );
+
+        assert_close(result[0].percentage, (80.0 + 50.0) / 2.0);
+    }
+
+    #[test]
+    fn late_submissions_keep_the_raw_score() {
+        let mut policy = policy(GradingMode::PercentAverage, vec![category(1, None)]);
+        policy.late_policies = vec![late_policy(None, 10.0)];
+        let mut due = assignment(1, 1, 10.0);
+        due.due_date = Some(date(10, 12));
+        let mut late = grade(1, 1, 10.0);
+        late.submitted_at = Some(date(13, 12));
+        let mut on_time = grade(2, 1, 10.0);
+        on_time.submitted_at = Some(date(10, 11));
+
+        let result = late_submissions(&policy, &[due], &[late, on_time]);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].student_id, 1);
+        assert_eq!(result[0].days_late, 3);
+        assert_close(result[0].penalty_percentage, 30.0);
+        assert_eq!(result[0].raw_score, Some(10.0));
+        assert_close(result[0].adjusted_score.unwrap(), 7.0);
+    }
+
+    fn accommodation(
+        student_id: i64,
+        class_id: Option<i64>,
+        extension_days: i64,
+    ) -> StudentAccommodation {
+        StudentAccommodation {
+            id: 0,
+            student_id,
+            class_id,
+            extension_days,
+            time_multiplier: 1.0,
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn effective_due_date_prefers_the_class_accommodation() {
+        let mut due = assignment(1, 1, 10.0);
+        due.due_date = Some(date(10, 12));
+        let accommodations = vec![
+            accommodation(1, None, 1),
+            accommodation(1, Some(CLASS_ID), 3),
+            accommodation(2, None, 2),
+            accommodation(3, Some(CLASS_ID + 1), 5),
+        ];
+
+        assert_eq!(
+            effective_due_date(&due, 1, &[], &accommodations),
+            Some(date(13, 12))
+        );
+        assert_eq!(
+            effective_due_date(&due, 2, &[], &accommodations),
+            Some(date(12, 12))
+        );
+        assert_eq!(
+            effective_due_date(&due, 3, &[], &accommodations),
+            Some(date(10, 12))
+        );
+        assert_eq!(
+            effective_due_date(&assignment(2, 1, 10.0), 1, &[], &accommodations),
+            None
+        );
+    }
+
+    #[test]
+    fn extensions_past_the_calendar_do_not_overflow() {
+        let mut due = assignment(1, 1, 10.0);
+        due.due_date = Some(date(10, 12));
+
+        for extension_days in [100_000_000, i64::MAX] {
+            let accommodations = vec![accommodation(1, None, extension_days)];
+            assert_eq!(effective_due_date(&due, 1, &[], &accommodations), None);
+        }
+    }
+
+    #[test]
+    fn accommodations_push_back_late_penalties() {
+        let mut policy = policy(GradingMode::PercentAverage, vec![category(1, None)]);
+        policy.late_policies = vec![late_policy(None, 10.0)];
+        policy.accommodations = vec![accommodation(1, None, 3)];
+        let mut due = assignment(1, 1, 10.0);
+        due.due_date = Some(date(10, 12));
+        let mut accommodated = grade(1, 1, 10.0);
+        accommodated.submitted_at = Some(date(13, 12));
+        let mut late = grade(2, 1, 10.0);
+        late.submitted_at = Some(date(13, 12));
+
+        let result = late_submissions(&policy, &[due], &[accommodated, late]);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].student_id, 2);
+        assert_close(result[0].penalty_percentage, 30.0);
+    }
+
+    #[test]
+    fn due_date_overrides_win_over_accommodations() {
+        let mut due = assignment(1, 1, 10.0);
+        due.due_date = Some(date(10, 12));
+        let overrides = vec![DueDateOverride {
+            student_id: 1,
+            assignment_id: 1,
+            due_date: date(11, 12),
+            reason: None,
+        }];
+        let accommodations = vec![accommodation(1, None, 3), accommodation(2, None, 3)];
+
+        assert_eq!(
+            effective_due_date(&due, 1, &overrides, &accommodations),
+            Some(date(11, 12))
+        );
+        assert_eq!(
+            effective_due_date(&due, 2, &overrides, &accommodations),
+            Some(date(13, 12))
+        );
+        assert_eq!(
+            effective_due_date(&assignment(1, 1, 10.0), 1, &overrides, &[]),
+            Some(date(11, 12))
+        );
+    }
+
+    #[test]
+    fn late_submissions_use_the_due_date_override() {
+        let mut policy = policy(GradingMode::PercentAverage, vec![category(1, None)]);
+        policy.late_policies = vec![late_policy(None, 10.0)];
+        policy.due_date_overrides = vec![DueDateOverride {
+            student_id: 1,
+            assignment_id: 1,
+            due_date: date(12, 12),
+            reason: Some("Illness".to_string()),
+        }];
+        let mut due = assignment(1, 1, 10.0);
+        due.due_date = Some(date(10, 12));
+        let mut extended = grade(1, 1, 10.0);
+        extended.submitted_at = Some(date(13, 12));
+
+        let result = late_submissions(&policy, &[due], &[extended]);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].due_date, date(12, 12));
+        assert_eq!(result[0].days_late, 1);
+        assert_close(result[0].penalty_percentage, 10.0);
+    }
+
+    fn curve(
+        assignment_id: i64,
+        curve_kind: CurveKind,
+        curve_value: Option<f64>,
+    ) -> AssignmentCurve {
+        AssignmentCurve {
+            assignment_id,
+            curve_kind,
+            curve_value,
+            applied_at: date(1, 0),
+        }
+    }
+
+    fn assert_all_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert_close(*actual, *expected);
+        }
+    }
+
+    #[test]
+    fn flat_add_curve_is_capped_at_the_maximum() {
+        let test = assignment(1, 1, 100.0);
+
+        let curved = curve_scores(CurveKind::FlatAdd, Some(10.0), &test, &[50.0, 95.0]);
+
+        assert_all_close(&curved, &[60.0, 100.0]);
+    }
+
+    #[test]
+    fn flat_add_curve_may_exceed_the_maximum_with_extra_credit() {
+        let mut test = assignment(1, 1, 100.0);
+        test.allows_extra_credit = true;
+
+        let curved = curve_scores(CurveKind::FlatAdd, Some(10.0), &test, &[95.0]);
+
+        assert_all_close(&curved, &[105.0]);
+    }
+
+    #[test]
+    fn scale_to_highest_curve_lifts_the_top_score_to_the_maximum() {
+        let test = assignment(1, 1, 100.0);
+
+        let curved = curve_scores(CurveKind::ScaleToHighest, None, &test, &[40.0, 80.0]);
+
+        assert_all_close(&curved, &[50.0, 100.0]);
+    }
+
+    #[test]
+    fn square_root_curve_takes_the_root_of_the_fraction() {
+        let test = assignment(1, 1, 100.0);
+
+        let curved = curve_scores(CurveKind::SquareRoot, None, &test, &[64.0, 0.0, 100.0]);
+
+        assert_all_close(&curved, &[80.0, 0.0, 100.0]);
+    }
+
+    #[test]
+    fn target_mean_curve_shifts_the_mean() {
+        let test = assignment(1, 10, 50.0);
+
+        let curved = curve_scores(CurveKind::TargetMean, Some(80.0), &test, &[30.0, 20.0]);
+
+        assert_all_close(&curved, &[45.0, 35.0]);
+    }
+
+    #[test]
+    fn preview_curve_leaves_ungraded_rows_alone() {
+        let test = assignment(1, 1, 100.0);
+        let grades = vec![
+            grade(1, 1, 50.0),
+            ungraded(2, 1, GradeStatus::Missing),
+            grade(3, 2, 10.0),
+        ];
+
+        let preview = preview_curve(CurveKind::FlatAdd, Some(5.0), &test, &grades);
+
+        assert_eq!(preview.len(), 2);
+        assert_eq!(preview[0].raw_score, Some(50.0));
+        assert_eq!(preview[0].curved_score, Some(55.0));
+        assert_eq!(preview[1].status, GradeStatus::Missing);
+        assert_eq!(preview[1].curved_score, None);
+    }
+
+    #[test]
+    fn overall_grades_use_curved_scores() {
+        let mut policy = policy(GradingMode::PercentAverage, vec![category(1, None)]);
+        policy.curves = vec![curve(1, CurveKind::ScaleToHighest, None)];
+        let assignments = vec![assignment(1, 1, 10.0)];
+        let grades = vec![grade(1, 1, 8.0), grade(2, 1, 4.0)];
+
+        let result = calculate_overall_grades(&policy, &[1, 2], &assignments, &grades);
+
+        assert_close(result[0].percentage, 100.0);
+        assert_close(result[1].percentage, 50.0);
+    }
+
+    #[test]
+    fn assignment_percentages_count_as_the_overall_grade_does() {
+        let mut policy = policy(GradingMode::PercentAverage, vec![category(1, None)]);
+        policy.curves = vec![curve(1, CurveKind::FlatAdd, Some(2.0))];
+        let test = assignment(1, 1, 10.0);
+        let grades = vec![
+            grade(1, 1, 6.0),
+            ungraded(2, 1, GradeStatus::Missing),
+            ungraded(3, 1, GradeStatus::Excused),
+            grade(4, 2, 10.0),
+        ];
+
+        let result = assignment_percentages(&policy, &[1, 2, 3, 4], &test, &grades);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].0, 1);
+        assert_close(result[0].1, 80.0);
+        assert_eq!(result[1].0, 2);
+        assert_close(result[1].1, 0.0);
+    }
+
+    #[test]
+    fn assignment_percentages_leave_out_students_no_longer_in_the_class() {
+        let policy = policy(GradingMode::PercentAverage, vec![category(1, None)]);
+        let test = assignment(1, 1, 10.0);
+        let grades = vec![grade(1, 1, 6.0), grade(2, 1, 9.0)];
+
+        let result = assignment_percentages(&policy, &[1], &test, &grades);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].0, 1);
+        assert_close(result[0].1, 60.0);
+    }
+
+    #[test]
+    fn letter_grade_uses_the_highest_threshold_reached() {
+        let scale = standard_scale();
+
+        assert_eq!(letter_grade(100.0, &scale), "A");
+        assert_eq!(letter_grade(90.0, &scale), "A");
+        assert_eq!(letter_grade(89.99, &scale), "B");
+        assert_eq!(letter_grade(0.0, &scale), "F");
+    }
+
+    #[test]
+    fn letter_grade_falls_back_to_the_lowest_threshold() {
+        let scale = vec![
+            GradeThreshold {
+                letter_grade: "P".to_string(),
+                min_percentage: 60.0,
+            },
+            GradeThreshold {
+                letter_grade: "NP".to_string(),
+                min_percentage: 30.0,
+            },
+        ];
+
+        assert_eq!(letter_grade(10.0, &scale), "NP");
+        assert_eq!(letter_grade(75.0, &scale), "P");
+        assert_eq!(letter_grade(75.0, &[]), "");
+    }
+}
@@ -1,18 +1,43 @@
-use crate::database::db::Database;
+use crate::database::{backup, db::Database, location, models::BackupReason};
+use std::path::PathBuf;
 use tauri::{async_runtime, Manager};
 use tauri_plugin_fs::FsExt;
-use tokio::{fs, sync::Mutex};
+use tokio::sync::Mutex;
 
 mod database {
+    pub mod archive;
+    pub mod backup;
     pub mod db;
+    pub mod location;
     pub mod models;
 }
 
+mod csv_file;
+mod error;
+mod grade_import;
+mod gradebook;
+mod grading;
+mod roster;
+mod statistics;
+mod validation;
+
 mod commands {
+    pub mod accommodations;
+    pub mod archive;
     pub mod assignments;
+    pub mod backups;
+    pub mod categories;
     pub mod classes;
+    pub mod curves;
+    pub mod due_date_overrides;
+    pub mod gradebook;
     pub mod grades;
+    pub mod grading_scales;
+    pub mod late_policies;
     pub mod overall_grades;
+    pub mod recovery;
+    pub mod roster;
+    pub mod statistics;
     pub mod student_classes;
     pub mod students;
 }
@@ -27,38 +52,73 @@ impl AppState {
     }
 }
 
+/// Where the database lives and, when it could not be opened, why. `AppState`
+/// is only managed once the database is open; until then the app runs in
+/// recovery mode.
+struct StartupState {
+    db_path: PathBuf,
+    app_data_dir: PathBuf,
+    error: Option<String>,
+    /// Why the last automatic backup failed, until one succeeds.
+    backup_error: Option<String>,
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_opener::init())
         .setup(|app| {
-            let scope = app.fs_scope();
-
-            let exe_path = std::env::current_exe().expect("Can't get exe path");
-            let install_dir = exe_path
-                .parent()
-                .expect("Can't find parent dir")
-                .to_path_buf();
-            let db_path = install_dir.join("db.sqlite");
-
-            scope
-                .allow_directory(&install_dir, true)
-                .expect("Can't allow install dir");
+            let app_data_dir = app.path().app_data_dir()?;
+            let (db_path, is_default_path) = match location::database_path_override(
+                std::env::args_os(),
+                std::env::var_os(location::DATABASE_PATH_ENV),
+            )
+            .or_else(|| location::saved_database_path(&app_data_dir))
+            {
+                Some(db_path) => (db_path, false),
+                None => (location::default_database_path(&app_data_dir), true),
+            };
+            if let Some(db_dir) = db_path.parent() {
+                app.fs_scope().allow_directory(db_dir, true)?;
+            }
 
-            async_runtime::block_on(async {
-                if !db_path.exists() {
-                    fs::write(&db_path, b"")
-                        .await
-                        .expect("Can't create db file");
+            let opened = async_runtime::block_on(async {
+                if is_default_path {
+                    if let Some(legacy_path) = location::legacy_database_path() {
+                        location::migrate_legacy_database(&legacy_path, &db_path)?;
+                    }
                 }
-
-                let db_url = format!("sqlite://{}", db_path.display());
-                let db = Database::new(&db_url).await;
-                let state = AppState::new(db);
-                app.manage(Mutex::new(state));
+                Database::open(&db_path).await
             });
 
+            let (error, backup_error) = match opened {
+                Ok(db) => {
+                    let backup_dir = backup::backup_dir(&db_path);
+                    let backup_error =
+                        async_runtime::block_on(commands::backups::automatic_backup(
+                            &db.pool,
+                            &backup_dir,
+                            BackupReason::Startup,
+                        ))
+                        .err()
+                        .map(|e| format!("Startup backup failed: {e}"));
+                    app.manage(Mutex::new(AppState::new(db)));
+                    (None, backup_error)
+                }
+                Err(e) => {
+                    eprintln!("Can't open database at {}: {e}", db_path.display());
+                    (Some(e.to_string()), None)
+                }
+            };
+            app.manage(Mutex::new(StartupState {
+                db_path,
+                app_data_dir,
+                error,
+                backup_error,
+            }));
+            async_runtime::spawn(commands::backups::run_backup_schedule(app.handle().clone()));
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -71,21 +131,70 @@ pub fn run() {
             commands::grades::get_grade,
             commands::grades::get_all_grades,
             commands::grades::update_grade,
+            commands::grades::set_grade,
             commands::grades::delete_grade,
+            commands::grades::save_grades_bulk,
+            commands::grades::import_assignment_grades_csv,
             commands::classes::create_class,
             commands::classes::get_class,
             commands::classes::get_all_classes,
             commands::classes::update_class,
+            commands::classes::set_class_grading_mode,
+            commands::classes::set_class_grading_scale,
             commands::classes::delete_class,
+            commands::grading_scales::create_grading_scale,
+            commands::grading_scales::get_grading_scale,
+            commands::grading_scales::get_all_grading_scales,
+            commands::grading_scales::update_grading_scale,
+            commands::grading_scales::delete_grading_scale,
+            commands::categories::create_category,
+            commands::categories::get_category,
+            commands::categories::get_categories,
+            commands::categories::get_all_categories,
+            commands::categories::update_category,
+            commands::categories::delete_category,
             commands::assignments::create_assignment,
             commands::assignments::get_assignment,
             commands::assignments::get_all_assignments,
             commands::assignments::update_assignment,
             commands::assignments::delete_assignment,
+            commands::accommodations::set_student_accommodation,
+            commands::accommodations::get_student_accommodations,
+            commands::accommodations::delete_student_accommodation,
+            commands::accommodations::get_upcoming_deadlines,
+            commands::curves::preview_curve,
+            commands::curves::apply_curve,
+            commands::curves::get_assignment_curve,
+            commands::curves::remove_curve,
+            commands::due_date_overrides::create_due_date_override,
+            commands::due_date_overrides::get_all_due_date_overrides,
+            commands::due_date_overrides::get_assignment_due_date_overrides,
+            commands::due_date_overrides::delete_due_date_override,
+            commands::late_policies::set_late_policy,
+            commands::late_policies::get_late_policies,
+            commands::late_policies::delete_late_policy,
+            commands::late_policies::get_late_submissions,
+            commands::statistics::get_class_statistics,
+            commands::statistics::get_assignment_statistics,
+            commands::statistics::get_class_distribution,
+            commands::statistics::get_assignment_distribution,
             commands::student_classes::enroll_student,
             commands::student_classes::get_enrollments,
             commands::student_classes::unenroll_student,
-            commands::overall_grades::get_overall_grades
+            commands::overall_grades::get_overall_grades,
+            commands::recovery::get_startup_error,
+            commands::recovery::restore_database,
+            commands::recovery::create_fresh_database,
+            commands::backups::list_backups,
+            commands::backups::create_backup,
+            commands::backups::restore_backup,
+            commands::backups::get_backup_settings,
+            commands::backups::update_backup_settings,
+            commands::backups::get_backup_error,
+            commands::archive::export_gradify_archive,
+            commands::archive::import_gradify_archive,
+            commands::roster::import_students_csv,
+            commands::gradebook::export_class_gradebook_csv
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
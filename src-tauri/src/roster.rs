@@ -0,0 +1,189 @@
+use crate::{
+    csv_file,
+    database::models::{RosterColumns, RosterImportRow, RosterRowStatus},
+    error::GradifyError,
+    validation,
+};
+use std::{collections::HashSet, io::Read};
+
+/// Reads the rows of a roster CSV with a header row. Rows that fail
+/// validation, or repeat an email address used earlier in the file, are
+/// marked `Invalid`; every other row is `New` until checked against the
+/// database. Blank rows are skipped.
+pub fn parse_roster(
+    reader: impl Read,
+    columns: &RosterColumns,
+) -> Result<Vec<RosterImportRow>, GradifyError> {
+    let mut csv = csv_file::reader(reader);
+    let headers = csv.headers().map_err(csv_file::unreadable)?.clone();
+    let column = |name: &str| csv_file::column(&headers, name);
+    let optional_column = |name: &Option<String>| name.as_deref().map(column).transpose();
+
+    enum Names {
+        Separate(usize, usize),
+        Full(usize),
+    }
+    let (names, email_column) = match columns {
+        RosterColumns::SeparateNames {
+            first_name,
+            last_name,
+            email,
+        } => (
+            Names::Separate(column(first_name)?, column(last_name)?),
+            optional_column(email)?,
+        ),
+        RosterColumns::FullName { full_name, email } => {
+            (Names::Full(column(full_name)?), optional_column(email)?)
+        }
+    };
+
+    let mut emails = HashSet::new();
+    let mut rows = Vec::new();
+    for record in csv.records() {
+        let record = record.map_err(csv_file::unreadable)?;
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+        let field = |index: usize| record.get(index).unwrap_or_default().to_string();
+
+        let (first_name, last_name) = match names {
+            Names::Separate(first, last) => (field(first), field(last)),
+            Names::Full(full) => split_full_name(&field(full)),
+        };
+        let email = email_column.map(field).filter(|email| !email.is_empty());
+
+        let error = validate_row(&first_name, &last_name, email.as_deref())
+            .err()
+            .map(|e| e.to_string())
+            .or_else(|| {
+                let email = email.as_ref()?.to_lowercase();
+                (!emails.insert(email)).then(|| "Email appears more than once".to_string())
+            });
+
+        rows.push(RosterImportRow {
+            line: record.position().map_or(0, |position| position.line()),
+            first_name,
+            last_name,
+            email,
+            status: match error {
+                Some(_) => RosterRowStatus::Invalid,
+                None => RosterRowStatus::New,
+            },
+            student_id: None,
+            error,
+        });
+    }
+
+    Ok(rows)
+}
+
+fn validate_row(
+    first_name: &str,
+    last_name: &str,
+    email: Option<&str>,
+) -> Result<(), GradifyError> {
+    validation::required_text("first_name", first_name)?;
+    validation::required_text("last_name", last_name)?;
+    validation::email(email)?;
+    Ok(())
+}
+
+/// "Last, First" or "First Last" as `(first, last)`. A single name is taken
+/// as the first name, leaving the last name empty.
+fn split_full_name(full_name: &str) -> (String, String) {
+    let (first, last) = match full_name.split_once(',') {
+        Some((last, first)) => (first, last),
+        None => match full_name.trim().rsplit_once(char::is_whitespace) {
+            Some((first, last)) => (first, last),
+            None => (full_name, ""),
+        },
+    };
+    (first.trim().to_string(), last.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn separate_names() -> RosterColumns {
+        RosterColumns::SeparateNames {
+            first_name: "First".to_string(),
+            last_name: "Last".to_string(),
+            email: Some("Email".to_string()),
+        }
+    }
+
+    #[test]
+    fn splits_full_names_either_way_round() {
+        assert_eq!(
+            split_full_name("Lovelace, Ada"),
+            ("Ada".to_string(), "Lovelace".to_string())
+        );
+        assert_eq!(
+            split_full_name("Ada King Lovelace"),
+            ("Ada King".to_string(), "Lovelace".to_string())
+        );
+        assert_eq!(split_full_name("Ada"), ("Ada".to_string(), String::new()));
+    }
+
+    #[test]
+    fn reads_mapped_columns_case_insensitively() {
+        let csv = "id,LAST,first,email\n1,Lovelace,Ada,ada@example.com\n,,,\n2,Hopper,Grace,\n";
+
+        let rows = parse_roster(csv.as_bytes(), &separate_names()).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].first_name, "Ada");
+        assert_eq!(rows[0].email.as_deref(), Some("ada@example.com"));
+        assert_eq!(rows[1].line, 4);
+        assert_eq!(rows[1].email, None);
+        assert!(rows.iter().all(|r| r.status == RosterRowStatus::New));
+    }
+
+    #[test]
+    fn reads_quoted_full_names() {
+        let columns = RosterColumns::FullName {
+            full_name: "Name".to_string(),
+            email: None,
+        };
+        let csv = "Name\n\"Lovelace, Ada\"\n";
+
+        let rows = parse_roster(csv.as_bytes(), &columns).unwrap();
+
+        assert_eq!(
+            (rows[0].first_name.as_str(), rows[0].last_name.as_str()),
+            ("Ada", "Lovelace")
+        );
+    }
+
+    #[test]
+    fn marks_invalid_and_repeated_rows() {
+        let csv = "First,Last,Email\nAda,,ada@example.com\nGrace,Hopper,not-an-email\n\
+                   Alan,Turing,alan@example.com\nAlan,Turing,ALAN@example.com\n";
+
+        let rows = parse_roster(csv.as_bytes(), &separate_names()).unwrap();
+
+        let statuses: Vec<_> = rows.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                RosterRowStatus::Invalid,
+                RosterRowStatus::Invalid,
+                RosterRowStatus::New,
+                RosterRowStatus::Invalid,
+            ]
+        );
+        assert_eq!(rows[0].error.as_deref(), Some("Last name cannot be empty"));
+        assert_eq!(
+            rows[3].error.as_deref(),
+            Some("Email appears more than once")
+        );
+    }
+
+    #[test]
+    fn missing_columns_are_reported() {
+        let error = parse_roster("Name,Email\n".as_bytes(), &separate_names()).unwrap_err();
+
+        assert_eq!(error.to_string(), "The file has no 'First' column");
+    }
+}
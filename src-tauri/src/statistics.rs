@@ -0,0 +1,293 @@
+use crate::{
+    database::models::{
+        Assignment, AssignmentStatistics, CategoryStatistics, ClassStatistics, Grade,
+        GradeThreshold, HistogramBucket, LetterGradeCount, OverallGrade, ScoreDistribution,
+        ScoreSummary, StudentStanding,
+    },
+    grading::{self, GradingPolicy},
+};
+
+/// Most buckets a histogram is split into, however far extra credit goes.
+const MAX_BUCKETS: usize = 200;
+
+/// Count, mean, median, population standard deviation, extremes and quartiles
+/// of `values`. Quartiles interpolate linearly between the closest values.
+pub fn summarize(values: &[f64]) -> ScoreSummary {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let count = sorted.len();
+    let mean = (count > 0).then(|| sorted.iter().sum::<f64>() / count as f64);
+    let std_dev = mean.map(|mean| {
+        let variance = sorted.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count as f64;
+        variance.sqrt()
+    });
+
+    ScoreSummary {
+        count,
+        mean,
+        median: quantile(&sorted, 0.5),
+        std_dev,
+        min: sorted.first().copied(),
+        max: sorted.last().copied(),
+        first_quartile: quantile(&sorted, 0.25),
+        third_quartile: quantile(&sorted, 0.75),
+    }
+}
+
+fn quantile(sorted: &[f64], q: f64) -> Option<f64> {
+    let last = sorted.len().checked_sub(1)?;
+    let position = q * last as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    let fraction = position - lower as f64;
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * fraction)
+}
+
+/// How many of `percentages` earn each letter of the scale, highest letter
+/// first. Letters nobody earned are listed with a count of zero.
+pub fn letter_distribution(
+    percentages: &[f64],
+    thresholds: &[GradeThreshold],
+) -> Vec<LetterGradeCount> {
+    let mut ordered: Vec<&GradeThreshold> = thresholds.iter().collect();
+    ordered.sort_by(|a, b| b.min_percentage.total_cmp(&a.min_percentage));
+
+    ordered
+        .into_iter()
+        .map(|threshold| LetterGradeCount {
+            letter_grade: threshold.letter_grade.clone(),
+            count: percentages
+                .iter()
+                .filter(|&&p| grading::letter_grade(p, thresholds) == threshold.letter_grade)
+                .count(),
+        })
+        .collect()
+}
+
+pub fn class_statistics(
+    policy: &GradingPolicy,
+    overall_grades: &[OverallGrade],
+) -> ClassStatistics {
+    let percentages: Vec<f64> = overall_grades.iter().map(|g| g.percentage).collect();
+
+    let categories = policy
+        .categories
+        .iter()
+        .map(|category| {
+            let category_percentages: Vec<f64> = overall_grades
+                .iter()
+                .flat_map(|g| &g.category_subtotals)
+                .filter(|s| s.category_id == category.id)
+                .filter_map(|s| s.percentage)
+                .collect();
+            CategoryStatistics {
+                category_id: category.id,
+                category_name: category.category_name.clone(),
+                summary: summarize(&category_percentages),
+            }
+        })
+        .collect();
+
+    ClassStatistics {
+        class_id: policy.class_id,
+        summary: summarize(&percentages),
+        letter_distribution: letter_distribution(&percentages, &policy.thresholds),
+        categories,
+    }
+}
+
+pub fn assignment_statistics(
+    policy: &GradingPolicy,
+    student_ids: &[i64],
+    assignment: &Assignment,
+    grades: &[Grade],
+) -> AssignmentStatistics {
+    let percentages: Vec<f64> =
+        grading::assignment_percentages(policy, student_ids, assignment, grades)
+            .into_iter()
+            .map(|(_, percentage)| percentage)
+            .collect();
+
+    AssignmentStatistics {
+        assignment_id: assignment.id,
+        class_id: assignment.class_id,
+        summary: summarize(&percentages),
+        letter_distribution: letter_distribution(&percentages, &policy.thresholds),
+    }
+}
+
+/// Buckets of `bucket_width` percentage points from 0 up to 100, or further
+/// when extra credit takes a percentage past 100, up to `MAX_BUCKETS` buckets.
+/// Negative percentages count towards the first bucket and percentages past
+/// the last bucket towards the last.
+pub fn histogram(percentages: &[f64], bucket_width: f64) -> Vec<HistogramBucket> {
+    let highest = percentages.iter().copied().fold(100.0, f64::max);
+    let bucket_count = ((highest / bucket_width).ceil() as usize).clamp(1, MAX_BUCKETS);
+
+    let mut buckets: Vec<HistogramBucket> = (0..bucket_count)
+        .map(|i| HistogramBucket {
+            lower: i as f64 * bucket_width,
+            upper: (i + 1) as f64 * bucket_width,
+            count: 0,
+        })
+        .collect();
+    for percentage in percentages {
+        let index = ((percentage / bucket_width).floor().max(0.0) as usize).min(bucket_count - 1);
+        buckets[index].count += 1;
+    }
+    buckets
+}
+
+/// Percentile rank and z-score of every student in `percentages`. The
+/// percentile rank counts ties as half below.
+pub fn standings(percentages: &[(i64, f64)]) -> Vec<StudentStanding> {
+    let values: Vec<f64> = percentages.iter().map(|(_, p)| *p).collect();
+    let summary = summarize(&values);
+    let count = values.len() as f64;
+
+    percentages
+        .iter()
+        .map(|&(student_id, percentage)| {
+            let below = values.iter().filter(|&&v| v < percentage).count() as f64;
+            let equal = values.iter().filter(|&&v| v == percentage).count() as f64;
+            let z_score = match (summary.mean, summary.std_dev) {
+                (Some(mean), Some(std_dev)) if std_dev > 0.0 => Some((percentage - mean) / std_dev),
+                _ => None,
+            };
+            StudentStanding {
+                student_id,
+                percentage,
+                percentile_rank: (below + equal / 2.0) / count * 100.0,
+                z_score,
+            }
+        })
+        .collect()
+}
+
+pub fn score_distribution(percentages: &[(i64, f64)], bucket_width: f64) -> ScoreDistribution {
+    let values: Vec<f64> = percentages.iter().map(|(_, p)| *p).collect();
+    ScoreDistribution {
+        bucket_width,
+        buckets: histogram(&values, bucket_width),
+        standings: standings(percentages),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("expected a value");
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    fn threshold(letter_grade: &str, min_percentage: f64) -> GradeThreshold {
+        GradeThreshold {
+            letter_grade: letter_grade.to_string(),
+            min_percentage,
+        }
+    }
+
+    #[test]
+    fn summarize_an_empty_set() {
+        let summary = summarize(&[]);
+
+        assert_eq!(summary.count, 0);
+        assert!(summary.mean.is_none());
+        assert!(summary.median.is_none());
+        assert!(summary.std_dev.is_none());
+        assert!(summary.first_quartile.is_none());
+    }
+
+    #[test]
+    fn summarize_computes_every_statistic() {
+        let summary = summarize(&[90.0, 70.0, 80.0, 60.0, 100.0]);
+
+        assert_eq!(summary.count, 5);
+        assert_close(summary.mean, 80.0);
+        assert_close(summary.median, 80.0);
+        assert_close(summary.std_dev, 200.0_f64.sqrt());
+        assert_close(summary.min, 60.0);
+        assert_close(summary.max, 100.0);
+        assert_close(summary.first_quartile, 70.0);
+        assert_close(summary.third_quartile, 90.0);
+    }
+
+    #[test]
+    fn quartiles_interpolate_between_values() {
+        let summary = summarize(&[10.0, 20.0, 30.0, 40.0]);
+
+        assert_close(summary.median, 25.0);
+        assert_close(summary.first_quartile, 17.5);
+        assert_close(summary.third_quartile, 32.5);
+    }
+
+    #[test]
+    fn letter_distribution_lists_every_letter() {
+        let scale = vec![
+            threshold("F", 0.0),
+            threshold("A", 90.0),
+            threshold("B", 80.0),
+        ];
+
+        let distribution = letter_distribution(&[95.0, 91.0, 50.0], &scale);
+
+        let counts: Vec<(&str, usize)> = distribution
+            .iter()
+            .map(|d| (d.letter_grade.as_str(), d.count))
+            .collect();
+        assert_eq!(counts, vec![("A", 2), ("B", 0), ("F", 1)]);
+    }
+
+    #[test]
+    fn histogram_puts_the_top_score_in_the_last_bucket() {
+        let buckets = histogram(&[0.0, 9.99, 10.0, 95.0, 100.0], 10.0);
+
+        assert_eq!(buckets.len(), 10);
+        assert_eq!(buckets[0].count, 2);
+        assert_eq!(buckets[1].count, 1);
+        assert_eq!(buckets[9].count, 2);
+        assert_eq!(buckets[9].upper, 100.0);
+    }
+
+    #[test]
+    fn histogram_grows_past_100_for_extra_credit() {
+        let buckets = histogram(&[100.0, 112.0], 25.0);
+
+        assert_eq!(buckets.len(), 5);
+        assert_eq!(buckets[4].lower, 100.0);
+        assert_eq!(buckets[4].count, 2);
+    }
+
+    #[test]
+    fn histogram_stops_growing_at_the_bucket_limit() {
+        let buckets = histogram(&[50.0, 1e12], 1.0);
+
+        assert_eq!(buckets.len(), MAX_BUCKETS);
+        assert_eq!(buckets[MAX_BUCKETS - 1].count, 1);
+    }
+
+    #[test]
+    fn standings_rank_and_standardise_each_student() {
+        let result = standings(&[(1, 60.0), (2, 80.0), (3, 80.0), (4, 100.0)]);
+
+        assert_close(Some(result[0].percentile_rank), 12.5);
+        assert_close(Some(result[1].percentile_rank), 50.0);
+        assert_close(Some(result[3].percentile_rank), 87.5);
+        assert_close(result[0].z_score, -20.0 / 200.0_f64.sqrt());
+        assert_close(result[1].z_score, 0.0);
+    }
+
+    #[test]
+    fn standings_have_no_z_score_without_spread() {
+        let result = standings(&[(1, 75.0), (2, 75.0)]);
+
+        assert!(result.iter().all(|s| s.z_score.is_none()));
+        assert_close(Some(result[0].percentile_rank), 50.0);
+    }
+}
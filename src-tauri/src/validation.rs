@@ -0,0 +1,224 @@
+use crate::error::GradifyError;
+use std::ops::RangeInclusive;
+
+/// "first_name" becomes "First name".
+fn label(field: &str) -> String {
+    let label = field.replace('_', " ");
+    let mut chars = label.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => label,
+    }
+}
+
+/// Trims `value`, which may not be left empty.
+pub fn required_text(field: &str, value: &str) -> Result<String, GradifyError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(GradifyError::validation(
+            field,
+            format!("{} cannot be empty", label(field)),
+        ));
+    }
+    Ok(value.to_string())
+}
+
+/// Trims `value`, treating blank text as no value at all.
+pub fn optional_text(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// Trims an optional email address and checks it has the shape
+/// `local@domain.tld`.
+pub fn email(value: Option<&str>) -> Result<Option<String>, GradifyError> {
+    let Some(email) = optional_text(value) else {
+        return Ok(None);
+    };
+
+    let valid = match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !domain.contains("..")
+                && !email.chars().any(char::is_whitespace)
+        }
+        None => false,
+    };
+    if !valid {
+        return Err(GradifyError::validation(
+            "email",
+            format!("'{email}' is not a valid email address"),
+        ));
+    }
+    Ok(Some(email))
+}
+
+pub fn positive_number(field: &str, value: f64) -> Result<f64, GradifyError> {
+    if !value.is_finite() || value <= 0.0 {
+        return Err(GradifyError::validation(
+            field,
+            format!("{} must be greater than zero", label(field)),
+        ));
+    }
+    Ok(value)
+}
+
+/// Checks that `value` is a finite number no smaller than `min`.
+pub fn number_at_least(field: &str, value: f64, min: f64) -> Result<f64, GradifyError> {
+    if !value.is_finite() || value < min {
+        return Err(GradifyError::validation(
+            field,
+            format!("{} must be a number of at least {min}", label(field)),
+        ));
+    }
+    Ok(value)
+}
+
+/// Checks that `value` is a number within `range`, bounds included.
+pub fn number_in(field: &str, value: f64, range: RangeInclusive<f64>) -> Result<f64, GradifyError> {
+    if !range.contains(&value) {
+        return Err(GradifyError::validation(
+            field,
+            format!(
+                "{} must be between {} and {}",
+                label(field),
+                range.start(),
+                range.end()
+            ),
+        ));
+    }
+    Ok(value)
+}
+
+/// Checks that a whole number lies within `range`, bounds included.
+pub fn whole_number_in(
+    field: &str,
+    value: i64,
+    range: RangeInclusive<i64>,
+) -> Result<i64, GradifyError> {
+    if !range.contains(&value) {
+        return Err(GradifyError::validation(
+            field,
+            format!(
+                "{} must be between {} and {}",
+                label(field),
+                range.start(),
+                range.end()
+            ),
+        ));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn required_text_is_trimmed() {
+        assert_eq!(
+            required_text("class_name", "  Algebra ").unwrap(),
+            "Algebra"
+        );
+    }
+
+    #[test]
+    fn required_text_rejects_whitespace() {
+        let error = required_text("class_name", " \t ").unwrap_err();
+
+        assert_eq!(error.to_string(), "Class name cannot be empty");
+    }
+
+    #[test]
+    fn blank_optional_text_is_none() {
+        assert_eq!(optional_text(Some("  ")), None);
+        assert_eq!(optional_text(Some(" Notes ")), Some("Notes".to_string()));
+        assert_eq!(optional_text(None), None);
+    }
+
+    #[test]
+    fn email_accepts_ordinary_addresses() {
+        assert_eq!(
+            email(Some(" jane.doe@school.edu ")).unwrap(),
+            Some("jane.doe@school.edu".to_string())
+        );
+        assert_eq!(email(Some("")).unwrap(), None);
+    }
+
+    #[test]
+    fn email_rejects_malformed_addresses() {
+        for address in [
+            "jane",
+            "@school.edu",
+            "jane@school",
+            "jane@@school.edu",
+            "jane@.edu",
+            "jane@school.edu.",
+            "jane doe@school.edu",
+        ] {
+            assert!(email(Some(address)).is_err(), "{address} was accepted");
+        }
+    }
+
+    #[test]
+    fn positive_number_rejects_zero_and_negatives() {
+        assert_eq!(positive_number("maximum_score", 10.0).unwrap(), 10.0);
+        assert!(positive_number("maximum_score", 0.0).is_err());
+        assert!(positive_number("maximum_score", -5.0).is_err());
+        assert!(positive_number("maximum_score", f64::NAN).is_err());
+    }
+
+    #[test]
+    fn number_at_least_includes_the_minimum() {
+        assert_eq!(number_at_least("time_multiplier", 1.0, 1.0).unwrap(), 1.0);
+        assert_eq!(number_at_least("time_multiplier", 1.5, 1.0).unwrap(), 1.5);
+        assert!(number_at_least("time_multiplier", f64::NAN, 1.0).is_err());
+        assert!(number_at_least("time_multiplier", f64::INFINITY, 1.0).is_err());
+
+        let error = number_at_least("time_multiplier", 0.5, 1.0).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Time multiplier must be a number of at least 1"
+        );
+    }
+
+    #[test]
+    fn number_in_rejects_values_outside_the_range() {
+        assert_eq!(
+            number_in("penalty_per_day", 12.5, 0.0..=100.0).unwrap(),
+            12.5
+        );
+        assert!(number_in("penalty_per_day", -0.5, 0.0..=100.0).is_err());
+        assert!(number_in("penalty_per_day", f64::NAN, 0.0..=100.0).is_err());
+
+        let error = number_in("grace_period_hours", 1e10, 0.0..=8760.0).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Grace period hours must be between 0 and 8760"
+        );
+    }
+
+    #[test]
+    fn whole_number_in_includes_both_bounds() {
+        assert_eq!(whole_number_in("extension_days", 0, 0..=365).unwrap(), 0);
+        assert_eq!(
+            whole_number_in("extension_days", 365, 0..=365).unwrap(),
+            365
+        );
+
+        let error = whole_number_in("extension_days", 366, 0..=365).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Extension days must be between 0 and 365"
+        );
+    }
+}
@@ -7,15 +7,25 @@ import { AlertCircle } from "lucide-react";
 import { getAllStudents } from "@/api/students";
 import { getAllClasses } from "@/api/classes";
 import { getAllAssignments } from "@/api/assignments";
+import { getAllCategories } from "@/api/categories";
 import { getOverallGrades } from "@/api/overall-grades";
+import { getStartupError } from "@/api/recovery";
+import { getBackupError } from "@/api/backups";
 
 import StudentsView from "@/components/students-view";
 import ClassesView from "@/components/classes-view";
 import AssignmentsView from "@/components/assignments-view";
 import GradesView from "@/components/grades-view";
 import DashboardView from "@/components/dashboard-view";
+import RecoveryView from "@/components/recovery-view";
 
-import type { Student, Class, Assignment, OverallGrade } from "@/api/types";
+import type {
+  Student,
+  Class,
+  Category,
+  Assignment,
+  OverallGrade,
+} from "@/api/types";
 import "./app.css";
 import { faSun, faMoon } from "@fortawesome/free-solid-svg-icons";
 import { FontAwesomeIcon } from "@fortawesome/react-fontawesome";
@@ -23,10 +33,13 @@ import { FontAwesomeIcon } from "@fortawesome/react-fontawesome";
 function App() {
   const [students, setStudents] = useState<Student[]>([]);
   const [classes, setClasses] = useState<Class[]>([]);
+  const [categories, setCategories] = useState<Category[]>([]);
   const [assignments, setAssignments] = useState<Assignment[]>([]);
   const [overallGrades, setOverallGrades] = useState<OverallGrade[]>([]);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
+  const [startupError, setStartupError] = useState<string | null>(null);
+  const [backupError, setBackupError] = useState<string | null>(null);
   const [activeTab, setActiveTab] = useState("dashboard");
   const [isDarkMode, setIsDarkMode] = useState(
     document.body.classList.contains("dark"),
@@ -53,6 +66,12 @@ function App() {
       setLoading(true);
       setError(null);
 
+      const startupErrorData = await getStartupError();
+      setStartupError(startupErrorData);
+      if (startupErrorData) return;
+
+      setBackupError(await getBackupError());
+
       try {
         const studentsData = await getAllStudents();
         setStudents(studentsData);
@@ -73,6 +92,17 @@ function App() {
         return;
       }
 
+      try {
+        const categoriesData = await getAllCategories();
+        setCategories(categoriesData);
+      } catch (err) {
+        console.error("Error loading categories:", err);
+        setError(
+          "Failed to load categories data. Please check database schema.",
+        );
+        return;
+      }
+
       try {
         const assignmentsData = await getAllAssignments();
         setAssignments(assignmentsData);
@@ -147,76 +177,89 @@ function App() {
           </Alert>
         )}
 
-        <Tabs
-          value={activeTab}
-          onValueChange={setActiveTab}
-          className="space-y-4"
-        >
-          <TabsList className="grid w-full grid-cols-5">
-            <TabsTrigger value="dashboard">Dashboard</TabsTrigger>
-            <TabsTrigger value="students">Students</TabsTrigger>
-            <TabsTrigger value="classes">Classes</TabsTrigger>
-            <TabsTrigger value="assignments">Assignments</TabsTrigger>
-            <TabsTrigger value="grades">Grades</TabsTrigger>
-          </TabsList>
-
-          <TabsContent value="dashboard" className="space-y-4">
-            <DashboardView
-              students={students}
-              classes={classes}
-              assignments={assignments}
-              overallGrades={overallGrades}
-              loading={loading}
-            />
-          </TabsContent>
-
-          <TabsContent value="students" className="space-y-4">
-            <StudentsView
-              students={students}
-              refreshData={refreshData}
-              loading={loading}
-            />
-          </TabsContent>
-
-          <TabsContent value="classes" className="space-y-4">
-            <ClassesView
-              classes={classes}
-              refreshData={refreshData}
-              loading={loading}
-            />
-          </TabsContent>
-
-          <TabsContent value="assignments" className="space-y-4">
-            <AssignmentsView
-              assignments={assignments}
-              classes={classes}
-              refreshData={refreshData}
-              loading={loading}
-            />
-          </TabsContent>
-
-          <TabsContent value="grades" className="space-y-4">
-            {classes.length > 0 && students.length > 0 ? (
-              <GradesView
-                grades={overallGrades}
+        {backupError && !startupError && (
+          <Alert variant="destructive" className="mb-6">
+            <AlertCircle className="h-4 w-4" />
+            <AlertTitle>Backup failed</AlertTitle>
+            <AlertDescription>{backupError}</AlertDescription>
+          </Alert>
+        )}
+
+        {startupError ? (
+          <RecoveryView startupError={startupError} onRecovered={loadData} />
+        ) : (
+          <Tabs
+            value={activeTab}
+            onValueChange={setActiveTab}
+            className="space-y-4"
+          >
+            <TabsList className="grid w-full grid-cols-5">
+              <TabsTrigger value="dashboard">Dashboard</TabsTrigger>
+              <TabsTrigger value="students">Students</TabsTrigger>
+              <TabsTrigger value="classes">Classes</TabsTrigger>
+              <TabsTrigger value="assignments">Assignments</TabsTrigger>
+              <TabsTrigger value="grades">Grades</TabsTrigger>
+            </TabsList>
+
+            <TabsContent value="dashboard" className="space-y-4">
+              <DashboardView
+                students={students}
+                classes={classes}
+                assignments={assignments}
+                overallGrades={overallGrades}
+                loading={loading}
+              />
+            </TabsContent>
+
+            <TabsContent value="students" className="space-y-4">
+              <StudentsView
                 students={students}
+                refreshData={refreshData}
+                loading={loading}
+              />
+            </TabsContent>
+
+            <TabsContent value="classes" className="space-y-4">
+              <ClassesView
                 classes={classes}
+                refreshData={refreshData}
+                loading={loading}
+              />
+            </TabsContent>
+
+            <TabsContent value="assignments" className="space-y-4">
+              <AssignmentsView
                 assignments={assignments}
+                classes={classes}
+                categories={categories}
                 refreshData={refreshData}
                 loading={loading}
               />
-            ) : (
-              <Alert className="mb-6">
-                <AlertCircle className="h-4 w-4" />
-                <AlertTitle>No data available</AlertTitle>
-                <AlertDescription>
-                  Please create at least one class and one student before
-                  accessing grades.
-                </AlertDescription>
-              </Alert>
-            )}
-          </TabsContent>
-        </Tabs>
+            </TabsContent>
+
+            <TabsContent value="grades" className="space-y-4">
+              {classes.length > 0 && students.length > 0 ? (
+                <GradesView
+                  grades={overallGrades}
+                  students={students}
+                  classes={classes}
+                  assignments={assignments}
+                  refreshData={refreshData}
+                  loading={loading}
+                />
+              ) : (
+                <Alert className="mb-6">
+                  <AlertCircle className="h-4 w-4" />
+                  <AlertTitle>No data available</AlertTitle>
+                  <AlertDescription>
+                    Please create at least one class and one student before
+                    accessing grades.
+                  </AlertDescription>
+                </Alert>
+              )}
+            </TabsContent>
+          </Tabs>
+        )}
       </main>
 
       <footer className="border-t bg-white dark:bg-gray-950 px-6 py-4 text-center text-sm text-gray-500 mt-auto">
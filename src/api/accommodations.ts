@@ -0,0 +1,42 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { StudentAccommodation, UpcomingDeadline } from "./types";
+
+// Only extension_days pushes due dates back; time_multiplier is stored for
+// reference and is not applied to anything.
+export async function setStudentAccommodation(
+  student_id: number,
+  class_id: number | undefined,
+  extension_days: number,
+  time_multiplier: number = 1,
+  notes?: string,
+): Promise<StudentAccommodation> {
+  return await invoke<StudentAccommodation>("set_student_accommodation", {
+    student_id,
+    class_id,
+    extension_days,
+    time_multiplier,
+    notes,
+  });
+}
+
+export async function getStudentAccommodations(
+  student_id: number,
+): Promise<StudentAccommodation[]> {
+  return await invoke<StudentAccommodation[]>("get_student_accommodations", {
+    student_id,
+  });
+}
+
+export async function deleteStudentAccommodation(id: number): Promise<void> {
+  return await invoke("delete_student_accommodation", { id });
+}
+
+export async function getUpcomingDeadlines(
+  student_id: number,
+  within_days?: number,
+): Promise<UpcomingDeadline[]> {
+  return await invoke<UpcomingDeadline[]>("get_upcoming_deadlines", {
+    student_id,
+    within_days,
+  });
+}
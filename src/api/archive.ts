@@ -0,0 +1,23 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { ArchiveManifest } from "./types";
+
+export async function exportGradifyArchive(
+  archive_path: string,
+): Promise<ArchiveManifest> {
+  return await invoke<ArchiveManifest>("export_gradify_archive", {
+    archive_path,
+  });
+}
+
+// Without a profile_path the current data is replaced (after a backup).
+// With one, the archive is written to a new database at that path and the
+// app switches to it, on this and later starts. Reload data afterwards.
+export async function importGradifyArchive(
+  archive_path: string,
+  profile_path?: string,
+): Promise<ArchiveManifest> {
+  return await invoke<ArchiveManifest>("import_gradify_archive", {
+    archive_path,
+    profile_path,
+  });
+}
@@ -1,19 +1,23 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { Assignment } from "./types";
+import type { Assignment, AssignmentKind } from "./types";
 
 export async function createAssignment(
   class_id: number,
   assignment_name: string,
-  assignment_type: string,
+  category_id: number,
   maximum_score: number,
   due_date?: string,
+  allows_extra_credit = false,
+  assignment_kind: AssignmentKind = "Regular",
 ): Promise<Assignment> {
   return await invoke<Assignment>("create_assignment", {
     class_id,
     assignment_name,
-    assignment_type,
+    category_id,
     maximum_score,
     due_date,
+    allows_extra_credit,
+    assignment_kind,
   });
 }
 
@@ -29,17 +33,21 @@ export async function updateAssignment(
   id: number,
   class_id: number,
   assignment_name: string,
-  assignment_type: string,
+  category_id: number,
   maximum_score: number,
   due_date?: string,
+  allows_extra_credit = false,
+  assignment_kind: AssignmentKind = "Regular",
 ): Promise<Assignment> {
   return await invoke<Assignment>("update_assignment", {
     id,
     class_id,
     assignment_name,
-    assignment_type,
+    category_id,
     maximum_score,
     due_date,
+    allows_extra_credit,
+    assignment_kind,
   });
 }
 
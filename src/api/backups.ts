@@ -0,0 +1,34 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { BackupInfo, BackupSettings } from "./types";
+
+export async function listBackups(): Promise<BackupInfo[]> {
+  return await invoke<BackupInfo[]>("list_backups");
+}
+
+export async function createBackup(): Promise<BackupInfo> {
+  return await invoke<BackupInfo>("create_backup");
+}
+
+export async function restoreBackup(file_name: string): Promise<void> {
+  return await invoke<void>("restore_backup", { file_name });
+}
+
+export async function getBackupSettings(): Promise<BackupSettings> {
+  return await invoke<BackupSettings>("get_backup_settings");
+}
+
+export async function updateBackupSettings(
+  interval_hours: number,
+  keep_daily: number,
+  keep_weekly: number,
+): Promise<BackupSettings> {
+  return await invoke<BackupSettings>("update_backup_settings", {
+    interval_hours,
+    keep_daily,
+    keep_weekly,
+  });
+}
+
+export async function getBackupError(): Promise<string | null> {
+  return await invoke<string | null>("get_backup_error");
+}
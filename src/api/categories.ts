@@ -0,0 +1,50 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { Category } from "./types";
+
+export async function createCategory(
+  class_id: number,
+  category_name: string,
+  weight?: number,
+  drop_lowest = 0,
+  drop_highest = 0,
+): Promise<Category> {
+  return await invoke<Category>("create_category", {
+    class_id,
+    category_name,
+    weight,
+    drop_lowest,
+    drop_highest,
+  });
+}
+
+export async function getCategory(id: number): Promise<Category> {
+  return await invoke<Category>("get_category", { id });
+}
+
+export async function getCategories(class_id: number): Promise<Category[]> {
+  return await invoke<Category[]>("get_categories", { class_id });
+}
+
+export async function getAllCategories(): Promise<Category[]> {
+  return await invoke<Category[]>("get_all_categories");
+}
+
+export async function updateCategory(
+  id: number,
+  category_name: string,
+  weight?: number,
+  drop_lowest = 0,
+  drop_highest = 0,
+): Promise<Category> {
+  return await invoke<Category>("update_category", {
+    id,
+    category_name,
+    weight,
+    drop_lowest,
+    drop_highest,
+  });
+}
+
+export async function deleteCategory(id: number): Promise<void> {
+  return await invoke("delete_category", { id });
+}
@@ -1,5 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { Class } from "./types";
+import type { Class, GradingMode } from "./types";
 
 export async function createClass(
   class_name: string,
@@ -24,6 +24,23 @@ export async function updateClass(
   return await invoke<Class>("update_class", { id, class_name, description });
 }
 
+export async function setClassGradingMode(
+  id: number,
+  grading_mode: GradingMode,
+): Promise<Class> {
+  return await invoke<Class>("set_class_grading_mode", { id, grading_mode });
+}
+
+export async function setClassGradingScale(
+  id: number,
+  grading_scale_id?: number,
+): Promise<Class> {
+  return await invoke<Class>("set_class_grading_scale", {
+    id,
+    grading_scale_id,
+  });
+}
+
 export async function deleteClass(id: number): Promise<void> {
   return await invoke("delete_class", { id });
 }
@@ -0,0 +1,38 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { AssignmentCurve, CurveKind, CurvedScore } from "./types";
+
+export async function previewCurve(
+  assignment_id: number,
+  curve_kind: CurveKind,
+  curve_value?: number,
+): Promise<CurvedScore[]> {
+  return await invoke<CurvedScore[]>("preview_curve", {
+    assignment_id,
+    curve_kind,
+    curve_value,
+  });
+}
+
+export async function applyCurve(
+  assignment_id: number,
+  curve_kind: CurveKind,
+  curve_value?: number,
+): Promise<AssignmentCurve> {
+  return await invoke<AssignmentCurve>("apply_curve", {
+    assignment_id,
+    curve_kind,
+    curve_value,
+  });
+}
+
+export async function getAssignmentCurve(
+  assignment_id: number,
+): Promise<AssignmentCurve | null> {
+  return await invoke<AssignmentCurve | null>("get_assignment_curve", {
+    assignment_id,
+  });
+}
+
+export async function removeCurve(assignment_id: number): Promise<void> {
+  return await invoke("remove_curve", { assignment_id });
+}
@@ -0,0 +1,38 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { DueDateOverride } from "./types";
+
+export async function createDueDateOverride(
+  student_id: number,
+  assignment_id: number,
+  due_date: string,
+  reason?: string,
+): Promise<DueDateOverride> {
+  return await invoke<DueDateOverride>("create_due_date_override", {
+    student_id,
+    assignment_id,
+    due_date,
+    reason,
+  });
+}
+
+export async function getAllDueDateOverrides(): Promise<DueDateOverride[]> {
+  return await invoke<DueDateOverride[]>("get_all_due_date_overrides");
+}
+
+export async function getAssignmentDueDateOverrides(
+  assignment_id: number,
+): Promise<DueDateOverride[]> {
+  return await invoke<DueDateOverride[]>("get_assignment_due_date_overrides", {
+    assignment_id,
+  });
+}
+
+export async function deleteDueDateOverride(
+  student_id: number,
+  assignment_id: number,
+): Promise<void> {
+  return await invoke("delete_due_date_override", {
+    student_id,
+    assignment_id,
+  });
+}
@@ -0,0 +1,14 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { GradebookExportOptions } from "./types";
+
+export async function exportClassGradebookCsv(
+  class_id: number,
+  file_path: string,
+  options?: GradebookExportOptions,
+): Promise<void> {
+  return await invoke<void>("export_class_gradebook_csv", {
+    class_id,
+    file_path,
+    options,
+  });
+}
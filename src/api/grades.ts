@@ -1,15 +1,24 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { Grade } from "./types";
+import type {
+  BulkGradeReport,
+  Grade,
+  GradeImportReport,
+  GradeStatus,
+} from "./types";
 
 export async function createGrade(
   student_id: number,
   assignment_id: number,
-  score: number,
+  score?: number,
+  status: GradeStatus = "Graded",
+  submitted_at?: string,
 ): Promise<Grade> {
   return await invoke<Grade>("create_grade", {
     student_id,
     assignment_id,
     score,
+    status,
+    submitted_at,
   });
 }
 
@@ -27,12 +36,32 @@ export async function getAllGrades(): Promise<Grade[]> {
 export async function updateGrade(
   student_id: number,
   assignment_id: number,
-  score: number,
+  score?: number,
+  status: GradeStatus = "Graded",
+  submitted_at?: string,
 ): Promise<Grade> {
   return await invoke<Grade>("update_grade", {
     student_id,
     assignment_id,
     score,
+    status,
+    submitted_at,
+  });
+}
+
+export async function setGrade(
+  student_id: number,
+  assignment_id: number,
+  score?: number,
+  status: GradeStatus = "Graded",
+  submitted_at?: string,
+): Promise<Grade> {
+  return await invoke<Grade>("set_grade", {
+    student_id,
+    assignment_id,
+    score,
+    status,
+    submitted_at,
   });
 }
 
@@ -42,3 +71,28 @@ export async function deleteGrade(
 ): Promise<void> {
   return await invoke("delete_grade", { student_id, assignment_id });
 }
+
+export async function saveGradesBulk(grades: Grade[]): Promise<BulkGradeReport> {
+  return await invoke<BulkGradeReport>("save_grades_bulk", { grades });
+}
+
+// Matches rows to the class's students by student ID or email. With only_empty
+// grades that already have a score or are excused are kept; with preview
+// nothing is saved.
+export async function importAssignmentGradesCsv(
+  assignment_id: number,
+  file_path: string,
+  student_column: string,
+  score_column: string,
+  only_empty = false,
+  preview = false,
+): Promise<GradeImportReport> {
+  return await invoke<GradeImportReport>("import_assignment_grades_csv", {
+    assignment_id,
+    file_path,
+    student_column,
+    score_column,
+    only_empty,
+    preview,
+  });
+}
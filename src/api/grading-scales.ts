@@ -0,0 +1,36 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { GradeThreshold, GradingScale } from "./types";
+
+export async function createGradingScale(
+  scale_name: string,
+  thresholds: GradeThreshold[],
+): Promise<GradingScale> {
+  return await invoke<GradingScale>("create_grading_scale", {
+    scale_name,
+    thresholds,
+  });
+}
+
+export async function getGradingScale(id: number): Promise<GradingScale> {
+  return await invoke<GradingScale>("get_grading_scale", { id });
+}
+
+export async function getAllGradingScales(): Promise<GradingScale[]> {
+  return await invoke<GradingScale[]>("get_all_grading_scales");
+}
+
+export async function updateGradingScale(
+  id: number,
+  scale_name: string,
+  thresholds: GradeThreshold[],
+): Promise<GradingScale> {
+  return await invoke<GradingScale>("update_grading_scale", {
+    id,
+    scale_name,
+    thresholds,
+  });
+}
+
+export async function deleteGradingScale(id: number): Promise<void> {
+  return await invoke("delete_grading_scale", { id });
+}
@@ -0,0 +1,34 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { LatePolicy, LateSubmission } from "./types";
+
+export async function setLatePolicy(
+  class_id: number,
+  assignment_id: number | undefined,
+  penalty_per_day: number,
+  max_penalty?: number,
+  grace_period_hours: number = 0,
+  zero_after_days?: number,
+): Promise<LatePolicy> {
+  return await invoke<LatePolicy>("set_late_policy", {
+    class_id,
+    assignment_id,
+    penalty_per_day,
+    max_penalty,
+    grace_period_hours,
+    zero_after_days,
+  });
+}
+
+export async function getLatePolicies(class_id: number): Promise<LatePolicy[]> {
+  return await invoke<LatePolicy[]>("get_late_policies", { class_id });
+}
+
+export async function deleteLatePolicy(id: number): Promise<void> {
+  return await invoke("delete_late_policy", { id });
+}
+
+export async function getLateSubmissions(
+  class_id: number,
+): Promise<LateSubmission[]> {
+  return await invoke<LateSubmission[]>("get_late_submissions", { class_id });
+}
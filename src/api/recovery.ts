@@ -0,0 +1,13 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export async function getStartupError(): Promise<string | null> {
+  return await invoke<string | null>("get_startup_error");
+}
+
+export async function restoreDatabase(backup_path: string): Promise<void> {
+  return await invoke<void>("restore_database", { backup_path });
+}
+
+export async function createFreshDatabase(): Promise<void> {
+  return await invoke<void>("create_fresh_database");
+}
@@ -0,0 +1,17 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { RosterColumns, RosterImportReport } from "./types";
+
+// With preview set nothing is written; the report shows what would happen.
+export async function importStudentsCsv(
+  file_path: string,
+  columns: RosterColumns,
+  class_id?: number,
+  preview = false,
+): Promise<RosterImportReport> {
+  return await invoke<RosterImportReport>("import_students_csv", {
+    file_path,
+    columns,
+    class_id,
+    preview,
+  });
+}
@@ -0,0 +1,40 @@
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  AssignmentStatistics,
+  ClassStatistics,
+  ScoreDistribution,
+} from "./types";
+
+export async function getClassStatistics(
+  class_id: number,
+): Promise<ClassStatistics> {
+  return await invoke<ClassStatistics>("get_class_statistics", { class_id });
+}
+
+export async function getAssignmentStatistics(
+  assignment_id: number,
+): Promise<AssignmentStatistics> {
+  return await invoke<AssignmentStatistics>("get_assignment_statistics", {
+    assignment_id,
+  });
+}
+
+export async function getClassDistribution(
+  class_id: number,
+  bucket_width?: number,
+): Promise<ScoreDistribution> {
+  return await invoke<ScoreDistribution>("get_class_distribution", {
+    class_id,
+    bucket_width,
+  });
+}
+
+export async function getAssignmentDistribution(
+  assignment_id: number,
+  bucket_width?: number,
+): Promise<ScoreDistribution> {
+  return await invoke<ScoreDistribution>("get_assignment_distribution", {
+    assignment_id,
+    bucket_width,
+  });
+}
@@ -5,26 +5,74 @@ export interface Student {
   email?: string;
 }
 
+export type GradeStatus = "Graded" | "Excused" | "Missing" | "Incomplete";
+
 export interface Grade {
   student_id: number;
   assignment_id: number;
-  score: number;
+  score?: number;
+  status: GradeStatus;
+  // ISO8601 timestamp of when the work was handed in, if recorded.
+  submitted_at?: string;
+}
+
+// error is unset for rows that were, or would have been, saved.
+export interface BulkGradeResult {
+  student_id: number;
+  assignment_id: number;
+  error?: string;
+}
+
+// Nothing is committed unless every row succeeds.
+export interface BulkGradeReport {
+  committed: boolean;
+  results: BulkGradeResult[];
 }
 
+export type GradingMode = "PercentAverage" | "TotalPoints" | "WeightedCategories";
+
 export interface Class {
   id: number;
   class_name: string;
   description?: string;
+  grading_mode: GradingMode;
+  grading_scale_id?: number;
+}
+
+export interface GradeThreshold {
+  letter_grade: string;
+  min_percentage: number;
 }
 
+export interface GradingScale {
+  id: number;
+  scale_name: string;
+  is_default: boolean;
+  thresholds: GradeThreshold[];
+}
+
+export interface Category {
+  id: number;
+  class_id: number;
+  category_name: string;
+  weight?: number;
+  drop_lowest: number;
+  drop_highest: number;
+}
+
+export type AssignmentKind = "Regular" | "ExtraCredit";
+
 export interface Assignment {
   id: number;
   class_id: number;
   assignment_name: string;
-  assignment_type: string;
+  category_id: number;
+  category_name: string;
   maximum_score: number;
   // Represent due_date as a string in ISO8601 format, or undefined if not set.
   due_date?: string;
+  allows_extra_credit: boolean;
+  assignment_kind: AssignmentKind;
 }
 
 export interface StudentClass {
@@ -32,9 +80,263 @@ export interface StudentClass {
   class_id: number;
 }
 
+// FlatAdd adds its value in points, TargetMean moves the mean to its value as
+// a percentage of the maximum score.
+export type CurveKind = "FlatAdd" | "ScaleToHighest" | "SquareRoot" | "TargetMean";
+
+export interface AssignmentCurve {
+  assignment_id: number;
+  curve_kind: CurveKind;
+  curve_value?: number;
+  applied_at: string;
+}
+
+export interface CurvedScore {
+  student_id: number;
+  status: GradeStatus;
+  raw_score?: number;
+  curved_score?: number;
+}
+
+export interface CategorySubtotal {
+  category_id: number;
+  category_name: string;
+  weight?: number;
+  graded_count: number;
+  points_earned: number;
+  points_possible: number;
+  percentage?: number;
+}
+
+export interface StudentAccommodation {
+  id: number;
+  student_id: number;
+  // Set for an accommodation in one class, unset when it applies to all.
+  class_id?: number;
+  extension_days: number;
+  // For reference only, e.g. 1.5 for time and a half; due dates only move by
+  // extension_days.
+  time_multiplier: number;
+  notes?: string;
+}
+
+export interface DueDateOverride {
+  student_id: number;
+  assignment_id: number;
+  due_date: string;
+  reason?: string;
+}
+
+export interface UpcomingDeadline {
+  student_id: number;
+  assignment_id: number;
+  assignment_name: string;
+  class_id: number;
+  // Unset when only the student's override gives the assignment a due date.
+  due_date?: string;
+  // The due date after the student's override or accommodation is applied.
+  effective_due_date: string;
+}
+
+export interface LatePolicy {
+  id: number;
+  class_id: number;
+  // Set for a per-assignment override, unset for the class-wide policy.
+  assignment_id?: number;
+  penalty_per_day: number;
+  max_penalty?: number;
+  grace_period_hours: number;
+  zero_after_days?: number;
+}
+
+export interface LateSubmission {
+  student_id: number;
+  assignment_id: number;
+  due_date: string;
+  submitted_at: string;
+  days_late: number;
+  penalty_percentage: number;
+  raw_score?: number;
+  adjusted_score?: number;
+}
+
 export interface OverallGrade {
   student_id: number;
   class_id: number;
+  grading_mode: GradingMode;
   percentage: number;
   letter_grade: string;
+  category_subtotals: CategorySubtotal[];
+  dropped_grades: Grade[];
+}
+
+// Statistics are over percentages; everything but count is unset when there
+// is nothing to summarise.
+export interface ScoreSummary {
+  count: number;
+  mean?: number;
+  median?: number;
+  std_dev?: number;
+  min?: number;
+  max?: number;
+  first_quartile?: number;
+  third_quartile?: number;
+}
+
+export interface LetterGradeCount {
+  letter_grade: string;
+  count: number;
+}
+
+export interface CategoryStatistics {
+  category_id: number;
+  category_name: string;
+  summary: ScoreSummary;
+}
+
+export interface ClassStatistics {
+  class_id: number;
+  summary: ScoreSummary;
+  letter_distribution: LetterGradeCount[];
+  categories: CategoryStatistics[];
+}
+
+export interface AssignmentStatistics {
+  assignment_id: number;
+  class_id: number;
+  summary: ScoreSummary;
+  letter_distribution: LetterGradeCount[];
+}
+
+// The last bucket of a histogram also holds its upper bound.
+export interface HistogramBucket {
+  lower: number;
+  upper: number;
+  count: number;
+}
+
+export interface StudentStanding {
+  student_id: number;
+  percentage: number;
+  percentile_rank: number;
+  // Unset when every student has the same percentage.
+  z_score?: number;
+}
+
+export interface ScoreDistribution {
+  bucket_width: number;
+  buckets: HistogramBucket[];
+  standings: StudentStanding[];
+}
+
+export type GradifyErrorKind =
+  | "NotFound"
+  | "Conflict"
+  | "Validation"
+  | "ScoreExceedsMax"
+  | "Database"
+  | "Io";
+
+// Every command rejects with this shape. field names the offending input for
+// Conflict, Validation and ScoreExceedsMax errors.
+export interface GradifyError {
+  kind: GradifyErrorKind;
+  field?: string;
+  message: string;
+}
+
+export type BackupReason =
+  | "Startup"
+  | "Scheduled"
+  | "Migration"
+  | "Restore"
+  | "Manual";
+
+export interface BackupInfo {
+  file_name: string;
+  path: string;
+  created_at: string;
+  reason: BackupReason;
+  size_bytes: number;
+}
+
+// interval_hours of 0 turns scheduled backups off.
+export interface BackupSettings {
+  interval_hours: number;
+  keep_daily: number;
+  keep_weekly: number;
+}
+
+// schema_version is the newest database migration the archive's data has had.
+export interface ArchiveManifest {
+  format_version: number;
+  schema_version: number;
+  app_version: string;
+  exported_at: string;
+  database_sha256: string;
+}
+
+// Which CSV columns, by header, hold a roster's names and emails. A full name
+// may be "Last, First" or "First Last".
+export type RosterColumns =
+  | {
+      kind: "SeparateNames";
+      first_name: string;
+      last_name: string;
+      email?: string;
+    }
+  | { kind: "FullName"; full_name: string; email?: string };
+
+export type RosterRowStatus =
+  | "New"
+  | "Existing"
+  | "AlreadyEnrolled"
+  | "Invalid";
+
+export interface RosterImportRow {
+  line: number;
+  first_name: string;
+  last_name: string;
+  email?: string;
+  status: RosterRowStatus;
+  student_id?: number;
+  error?: string;
+}
+
+// Nothing is committed unless every row is valid.
+export interface RosterImportReport {
+  committed: boolean;
+  rows: RosterImportRow[];
+}
+
+// Without status_marks, excused, missing and incomplete work is left blank
+// rather than marked "EX", "M" or "I".
+export interface GradebookExportOptions {
+  status_marks?: boolean;
+  max_points_row?: boolean;
+}
+
+// New and Replaces rows are saved; Kept rows leave a grade that already has a
+// score alone.
+export type GradeImportStatus =
+  | "New"
+  | "Replaces"
+  | "Kept"
+  | "Unmatched"
+  | "Invalid";
+
+// student is the email address or student ID as written in the file.
+export interface GradeImportRow {
+  line: number;
+  student: string;
+  student_id?: number;
+  score?: number;
+  status: GradeImportStatus;
+  error?: string;
+}
+
+export interface GradeImportReport {
+  committed: boolean;
+  imported: number;
+  rows: GradeImportRow[];
 }
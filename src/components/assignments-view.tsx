@@ -41,12 +41,13 @@ import {
   updateAssignment,
   deleteAssignment,
 } from "@/api/assignments";
-import type { Assignment, Class } from "@/api/types";
+import type { Assignment, Category, Class } from "@/api/types";
 import { DatePicker } from "./date-picker";
 
 interface AssignmentsViewProps {
   assignments: Assignment[];
   classes: Class[];
+  categories: Category[];
   refreshData: () => Promise<void>;
   loading: boolean;
 }
@@ -54,6 +55,7 @@ interface AssignmentsViewProps {
 export default function AssignmentsView({
   assignments,
   classes,
+  categories,
   refreshData,
   loading,
 }: AssignmentsViewProps) {
@@ -66,20 +68,20 @@ export default function AssignmentsView({
   );
 
   const [assignmentName, setAssignmentName] = useState("");
-  const [assignmentType, setAssignmentType] = useState<string>("Homework");
+  const [categoryId, setCategoryId] = useState<number | null>(null);
   const [classId, setClassId] = useState<number | null>(null);
   const [maximumScore, setMaximumScore] = useState<number>(100);
   const [dueDate, setDueDate] = useState<Date | undefined>(undefined);
 
-  const assignmentTypes = ["Homework", "Test"];
+  const classCategories = categories.filter((c) => c.class_id === classId);
 
   const filteredAssignments = assignments.filter((assignment) => {
     const assignmentNameLower = assignment.assignment_name.toLowerCase();
-    const assignmentTypeLower = assignment.assignment_type.toLowerCase();
+    const categoryNameLower = assignment.category_name.toLowerCase();
     const query = searchQuery.toLowerCase();
 
     return (
-      assignmentNameLower.includes(query) || assignmentTypeLower.includes(query)
+      assignmentNameLower.includes(query) || categoryNameLower.includes(query)
     );
   });
 
@@ -99,7 +101,7 @@ export default function AssignmentsView({
 
   const openCreateDialog = () => {
     setAssignmentName("");
-    setAssignmentType("Homework");
+    setCategoryId(null);
     setClassId(null);
     setMaximumScore(100);
     setDueDate(undefined);
@@ -109,7 +111,7 @@ export default function AssignmentsView({
   const openEditDialog = (assignment: Assignment) => {
     setCurrentAssignment(assignment);
     setAssignmentName(assignment.assignment_name);
-    setAssignmentType(assignment.assignment_type);
+    setCategoryId(assignment.category_id);
     setClassId(assignment.class_id);
     setMaximumScore(assignment.maximum_score);
     setDueDate(assignment.due_date ? new Date(assignment.due_date) : undefined);
@@ -122,7 +124,7 @@ export default function AssignmentsView({
   };
 
   const handleCreateAssignment = async () => {
-    if (!classId) return;
+    if (!classId || !categoryId) return;
 
     try {
       let dueDateString = undefined;
@@ -133,7 +135,7 @@ export default function AssignmentsView({
       await createAssignment(
         classId,
         assignmentName,
-        assignmentType,
+        categoryId,
         maximumScore,
         dueDateString,
       );
@@ -145,7 +147,7 @@ export default function AssignmentsView({
   };
 
   const handleUpdateAssignment = async () => {
-    if (!currentAssignment || !classId) return;
+    if (!currentAssignment || !classId || !categoryId) return;
 
     try {
       let dueDateString = undefined;
@@ -157,9 +159,11 @@ export default function AssignmentsView({
         currentAssignment.id,
         classId,
         assignmentName,
-        assignmentType,
+        categoryId,
         maximumScore,
         dueDateString,
+        currentAssignment.allows_extra_credit,
+        currentAssignment.assignment_kind,
       );
       await refreshData();
       setIsEditDialogOpen(false);
@@ -225,7 +229,7 @@ export default function AssignmentsView({
                 <TableRow>
                   <TableHead>ID</TableHead>
                   <TableHead>Assignment Name</TableHead>
-                  <TableHead>Type</TableHead>
+                  <TableHead>Category</TableHead>
                   <TableHead>Class</TableHead>
                   <TableHead>Max Score</TableHead>
                   <TableHead>Due Date</TableHead>
@@ -240,7 +244,7 @@ export default function AssignmentsView({
                         {assignment.id}
                       </TableCell>
                       <TableCell>{assignment.assignment_name}</TableCell>
-                      <TableCell>{assignment.assignment_type}</TableCell>
+                      <TableCell>{assignment.category_name}</TableCell>
                       <TableCell>{getClassName(assignment.class_id)}</TableCell>
                       <TableCell>{assignment.maximum_score}</TableCell>
                       <TableCell>{formatDate(assignment.due_date)}</TableCell>
@@ -319,6 +323,7 @@ export default function AssignmentsView({
                 onValueChange={(value) => {
                   if (value !== "selectClass") {
                     setClassId(parseInt(value));
+                    setCategoryId(null);
                   }
                 }}
               >
@@ -338,20 +343,27 @@ export default function AssignmentsView({
               </Select>
             </div>
             <div className="grid grid-cols-4 items-center gap-4">
-              <Label htmlFor="assignmentType" className="text-right">
-                Type
+              <Label htmlFor="assignmentCategory" className="text-right">
+                Category
               </Label>
               <Select
-                value={assignmentType || "Homework"}
-                onValueChange={(value) => setAssignmentType(value)}
+                value={categoryId?.toString() || "selectCategory"}
+                onValueChange={(value) => {
+                  if (value !== "selectCategory") {
+                    setCategoryId(parseInt(value));
+                  }
+                }}
               >
                 <SelectTrigger className="col-span-3">
-                  <SelectValue placeholder="Select a type" />
+                  <SelectValue placeholder="Select a category" />
                 </SelectTrigger>
                 <SelectContent>
-                  {assignmentTypes.map((type) => (
-                    <SelectItem key={type} value={type}>
-                      {type}
+                  {classCategories.map((category) => (
+                    <SelectItem
+                      key={category.id}
+                      value={category.id.toString()}
+                    >
+                      {category.category_name}
                     </SelectItem>
                   ))}
                 </SelectContent>
@@ -389,7 +401,7 @@ export default function AssignmentsView({
             </Button>
             <Button
               onClick={handleCreateAssignment}
-              disabled={!assignmentName || !assignmentType || !classId}
+              disabled={!assignmentName || !categoryId || !classId}
             >
               Create Assignment
             </Button>
@@ -427,6 +439,7 @@ export default function AssignmentsView({
                 onValueChange={(value) => {
                   if (value !== "selectClass") {
                     setClassId(parseInt(value));
+                    setCategoryId(null);
                   }
                 }}
               >
@@ -446,20 +459,27 @@ export default function AssignmentsView({
               </Select>
             </div>
             <div className="grid grid-cols-4 items-center gap-4">
-              <Label htmlFor="edit-assignmentType" className="text-right">
-                Type
+              <Label htmlFor="edit-assignmentCategory" className="text-right">
+                Category
               </Label>
               <Select
-                value={assignmentType || "Homework"}
-                onValueChange={(value) => setAssignmentType(value)}
+                value={categoryId?.toString() || "selectCategory"}
+                onValueChange={(value) => {
+                  if (value !== "selectCategory") {
+                    setCategoryId(parseInt(value));
+                  }
+                }}
               >
                 <SelectTrigger className="col-span-3">
-                  <SelectValue placeholder="Select a type" />
+                  <SelectValue placeholder="Select a category" />
                 </SelectTrigger>
                 <SelectContent>
-                  {assignmentTypes.map((type) => (
-                    <SelectItem key={type} value={type}>
-                      {type}
+                  {classCategories.map((category) => (
+                    <SelectItem
+                      key={category.id}
+                      value={category.id.toString()}
+                    >
+                      {category.category_name}
                     </SelectItem>
                   ))}
                 </SelectContent>
@@ -498,7 +518,7 @@ export default function AssignmentsView({
             </Button>
             <Button
               onClick={handleUpdateAssignment}
-              disabled={!assignmentName || !assignmentType || !classId}
+              disabled={!assignmentName || !categoryId || !classId}
             >
               Update Assignment
             </Button>
@@ -37,7 +37,7 @@ import { Skeleton } from "@/components/ui/skeleton";
 import { PlusCircle, Search, Pencil, AlertCircle } from "lucide-react";
 import { Badge } from "@/components/ui/badge";
 import { Alert, AlertDescription, AlertTitle } from "@/components/ui/alert";
-import { createGrade, updateGrade, getGrade, getAllGrades } from "@/api/grades";
+import { setGrade, updateGrade, getAllGrades } from "@/api/grades";
 import {
   enrollStudent,
   unenrollStudent,
@@ -293,12 +293,7 @@ export default function GradesView({
     setError(null);
 
     try {
-      try {
-        await getGrade(selectedStudent, selectedAssignment);
-        await updateGrade(selectedStudent, selectedAssignment, score);
-      } catch (error) {
-        await createGrade(selectedStudent, selectedAssignment, score);
-      }
+      await setGrade(selectedStudent, selectedAssignment, score);
 
       await refreshData();
       await loadAllGrades();
@@ -313,7 +308,11 @@ export default function GradesView({
 
   const handleGradeChange = (index: number, newScore: number) => {
     const updatedGrades = [...editingGrades];
-    updatedGrades[index] = { ...updatedGrades[index], score: newScore };
+    updatedGrades[index] = {
+      ...updatedGrades[index],
+      score: newScore,
+      status: "Graded",
+    };
     setEditingGrades(updatedGrades);
   };
 
@@ -323,7 +322,13 @@ export default function GradesView({
 
     try {
       for (const grade of editingGrades) {
-        await updateGrade(grade.student_id, grade.assignment_id, grade.score);
+        await updateGrade(
+          grade.student_id,
+          grade.assignment_id,
+          grade.score,
+          grade.status,
+          grade.submitted_at,
+        );
       }
       await refreshData();
       await loadAllGrades();
@@ -711,7 +716,7 @@ export default function GradesView({
               editingGrades.map((grade, index) => {
                 const maxScore = getAssignmentMaxScore(grade.assignment_id);
                 const percentage =
-                  maxScore > 0 ? (grade.score / maxScore) * 100 : 0;
+                  maxScore > 0 ? ((grade.score ?? 0) / maxScore) * 100 : 0;
 
                 return (
                   <div
@@ -727,7 +732,7 @@ export default function GradesView({
                       <Input
                         id={`grade-${index}`}
                         type="number"
-                        value={grade.score === 0 ? "" : grade.score}
+                        value={!grade.score ? "" : grade.score}
                         onChange={(e) =>
                           handleGradeChange(index, Number(e.target.value))
                         }
@@ -0,0 +1,131 @@
+import { useEffect, useState } from "react";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardFooter,
+  CardHeader,
+  CardTitle,
+} from "@/components/ui/card";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Alert, AlertTitle, AlertDescription } from "@/components/ui/alert";
+import { AlertCircle } from "lucide-react";
+import { createFreshDatabase, restoreDatabase } from "@/api/recovery";
+import { listBackups } from "@/api/backups";
+import type { BackupInfo, GradifyError } from "@/api/types";
+
+interface RecoveryViewProps {
+  startupError: string;
+  onRecovered: () => Promise<void>;
+}
+
+export default function RecoveryView({
+  startupError,
+  onRecovered,
+}: RecoveryViewProps) {
+  const [backupPath, setBackupPath] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const [working, setWorking] = useState(false);
+  const [backups, setBackups] = useState<BackupInfo[]>([]);
+
+  useEffect(() => {
+    listBackups()
+      .then(setBackups)
+      .catch((err) => console.error("Error loading backups:", err));
+  }, []);
+
+  const recover = async (action: () => Promise<void>) => {
+    try {
+      setWorking(true);
+      setError(null);
+      await action();
+      await onRecovered();
+    } catch (err) {
+      console.error("Error recovering database:", err);
+      setError((err as GradifyError).message ?? String(err));
+    } finally {
+      setWorking(false);
+    }
+  };
+
+  return (
+    <Card className="max-w-2xl mx-auto">
+      <CardHeader>
+        <CardTitle>The database could not be opened</CardTitle>
+        <CardDescription>
+          Gradify started without your data. Restore a backup or start over
+          with an empty database. The current database file is kept next to
+          the new one and is not deleted.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <Alert variant="destructive">
+          <AlertCircle className="h-4 w-4" />
+          <AlertTitle>Error</AlertTitle>
+          <AlertDescription>{startupError}</AlertDescription>
+        </Alert>
+        {error && (
+          <Alert variant="destructive">
+            <AlertCircle className="h-4 w-4" />
+            <AlertTitle>Recovery failed</AlertTitle>
+            <AlertDescription>{error}</AlertDescription>
+          </Alert>
+        )}
+        {backups.length > 0 && (
+          <div className="space-y-2">
+            <Label>Backups</Label>
+            <ul className="divide-y rounded-md border">
+              {backups.map((backup) => (
+                <li
+                  key={backup.file_name}
+                  className="flex items-center justify-between px-3 py-2"
+                >
+                  <span className="text-sm">
+                    {new Date(backup.created_at).toLocaleString()} (
+                    {backup.reason})
+                  </span>
+                  <Button
+                    size="sm"
+                    variant="outline"
+                    onClick={() => recover(() => restoreDatabase(backup.path))}
+                    disabled={working}
+                  >
+                    Restore
+                  </Button>
+                </li>
+              ))}
+            </ul>
+          </div>
+        )}
+        <div className="space-y-2">
+          <Label htmlFor="backup-path">Backup file</Label>
+          <div className="flex gap-2">
+            <Input
+              id="backup-path"
+              value={backupPath}
+              onChange={(e) => setBackupPath(e.target.value)}
+              placeholder="/path/to/gradify-backup.db"
+            />
+            <Button
+              onClick={() => recover(() => restoreDatabase(backupPath.trim()))}
+              disabled={working || !backupPath.trim()}
+            >
+              Restore
+            </Button>
+          </div>
+        </div>
+      </CardContent>
+      <CardFooter>
+        <Button
+          variant="outline"
+          onClick={() => recover(createFreshDatabase)}
+          disabled={working}
+        >
+          Create fresh database
+        </Button>
+      </CardFooter>
+    </Card>
+  );
+}